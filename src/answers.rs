@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use crate::days::Day;
+use crate::error::AocError;
+use crate::utils::read_input;
+
+/// Where the answers for our own inputs are kept, if anywhere
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known answers for our own puzzle inputs, read from a small subset of TOML:
+///
+/// ```toml
+/// [1]
+/// part1 = 444019
+/// part2 = 29212176
+///
+/// [15-dict]
+/// part1 = "1238"
+/// ```
+///
+/// A variant like "15-dict" falls back to the answers of its plain day if it has no section of its
+/// own.
+pub struct Answers(HashMap<String, HashMap<u8, String>>);
+
+impl Answers
+{
+  pub fn parse(text: &str) -> Result<Self, AocError>
+  {
+    let mut answers = HashMap::<String, HashMap<u8, String>>::new();
+    let mut section: Option<String> = None;
+
+    for (i, line) in text.lines().enumerate() {
+      let line = line.trim();
+      let error = |reason: &str| AocError::parse(i + 1, line, reason);
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+
+      if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+        let name = name.trim().trim_matches('"').to_string();
+        answers.entry(name.clone()).or_default();
+        section = Some(name);
+        continue;
+      }
+
+      let name = section.as_ref().ok_or_else(|| error("Answer outside of a [day] section"))?;
+      let (key, value) = line.split_once('=').ok_or_else(|| error("Expected \"part1 = <answer>\""))?;
+      let part = match key.trim() {
+        "part1" => 1,
+        "part2" => 2,
+        _ => return Err(error("Expected part1 or part2"))
+      };
+      let value = value.trim();
+      let value = match value.strip_prefix('"') {
+        Some(quoted) => &quoted[..quoted.find('"').ok_or_else(|| error("Unterminated string"))?],
+        None => value.split('#').next().unwrap_or_default().trim()
+      };
+      answers.entry(name.clone()).or_default().insert(part, value.to_string());
+    }
+
+    Ok(Answers(answers))
+  }
+
+  /// Read the answers from a file, if it exists
+  pub fn read<P>(path: P) -> Result<Option<Self>, AocError>
+    where P: AsRef<Path>
+  {
+    match read_input(path) {
+      Ok(text) => Ok(Some(Self::parse(&text)?)),
+      Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+      Err(e) => Err(e.into())
+    }
+  }
+
+  /// The known answer to one part of a day's puzzle
+  pub fn get(&self, day: &Day, part: u8) -> Option<&str>
+  {
+    [day.name(), day.number.to_string()].iter()
+      .find_map(|name| self.0.get(name)?.get(&part))
+      .map(String::as_str)
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::days;
+  use crate::inputs::{InputStore, DEFAULT_NAME};
+
+  #[test]
+  fn test_parse()
+  {
+    let answers = Answers::parse(r#"
+# Our answers
+[1]
+part1 = 444019
+part2 = "29212176" # the product of three entries
+
+[15-dict]
+part2 = 1
+"#).unwrap();
+
+    let day1 = days::find("1").unwrap();
+    assert_eq!(answers.get(&day1, 1), Some("444019"));
+    assert_eq!(answers.get(&day1, 2), Some("29212176"));
+    assert_eq!(answers.get(&days::find("2").unwrap(), 1), None);
+    assert_eq!(answers.get(&days::find("15-dict").unwrap(), 2), Some("1"));
+    assert_eq!(answers.get(&days::find("15").unwrap(), 2), None);
+
+    assert!(matches!(Answers::parse("part1 = 1"), Err(AocError::Parse { line: 1, .. })));
+    assert!(matches!(Answers::parse("[1]\npart3 = 1"), Err(AocError::Parse { line: 2, .. })));
+  }
+
+  /// Check our own inputs against the answers file, if there is one
+  #[test]
+  fn test_answers_file()
+  {
+    let answers = match Answers::read(ANSWERS_FILE).unwrap() {
+      Some(answers) => answers,
+      None => return
+    };
+
+    for day in days::all() {
+      let parts = [1, 2].iter().copied().filter(|&p| answers.get(&day, p).is_some()).collect::<Vec<_>>();
+      if parts.is_empty() {
+        continue;
+      }
+
+      let input = InputStore::from_env().read(day.number, DEFAULT_NAME).unwrap();
+      for answer in day.run(&input, &parts).unwrap().answers {
+        assert_eq!(Some(answer.value.as_str()), answers.get(&day, answer.part), "Day {} part {}", day.name(), answer.part);
+      }
+    }
+  }
+}
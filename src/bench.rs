@@ -0,0 +1,132 @@
+use std::time::Duration;
+use crate::days::Day;
+use crate::error::AocError;
+
+/// The spread of timings for one stage of a solution
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stats
+{
+  pub min: Duration,
+  pub median: Duration,
+  pub max: Duration,
+}
+
+impl Stats
+{
+  /// Summarize a set of timings, or return None if there are none
+  pub fn from_timings(timings: &[Duration]) -> Option<Self>
+  {
+    let mut sorted = timings.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    let median = match sorted.len() {
+      0 => return None,
+      n if n % 2 == 0 => (sorted[mid - 1] + sorted[mid]) / 2,
+      _ => sorted[mid]
+    };
+    Some(Stats { min: sorted[0], median, max: sorted[sorted.len() - 1] })
+  }
+
+  fn to_json(self) -> String
+  {
+    format!("{{\"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}}}",
+            self.min.as_nanos(), self.median.as_nanos(), self.max.as_nanos())
+  }
+}
+
+/// Timings of every stage of one day's solution
+pub struct Benchmark
+{
+  pub day: String,
+  /// (stage name, stats), where the stages are "parse", "part1" and "part2"
+  pub stages: Vec<(&'static str, Stats)>,
+}
+
+/// Run a day's solution on an input the given amount of times, and time each stage separately
+pub fn bench(day: &Day, input: &str, iterations: usize) -> Result<Benchmark, AocError>
+{
+  let mut parse = Vec::with_capacity(iterations);
+  let mut parts = [Vec::with_capacity(iterations), Vec::with_capacity(iterations)];
+
+  for _ in 0..iterations {
+    let run = day.run(input, &[1, 2])?;
+    parse.push(run.parse_time);
+    for answer in run.answers {
+      parts[answer.part as usize - 1].push(answer.time);
+    }
+  }
+
+  let stages = vec![("parse", &parse), ("part1", &parts[0]), ("part2", &parts[1])]
+    .into_iter()
+    .filter_map(|(name, timings)| Some((name, Stats::from_timings(timings)?)))
+    .collect();
+  Ok(Benchmark { day: day.name(), stages })
+}
+
+/// Lay out the benchmarks as a table with one row per stage
+pub fn to_table(benchmarks: &[Benchmark]) -> String
+{
+  let mut table = format!("{:<10}{:<8}{:>14}{:>14}{:>14}\n", "Day", "Stage", "Min", "Median", "Max");
+  for b in benchmarks {
+    for (stage, stats) in &b.stages {
+      table += &format!("{:<10}{:<8}{:>14}{:>14}{:>14}\n", b.day, stage,
+                        format!("{:.2?}", stats.min), format!("{:.2?}", stats.median), format!("{:.2?}", stats.max));
+    }
+  }
+  table
+}
+
+/// Serialize the benchmarks as JSON, with all timings in nanoseconds
+pub fn to_json(benchmarks: &[Benchmark], iterations: usize) -> String
+{
+  let days = benchmarks.iter()
+    .map(|b| {
+      let stages = b.stages.iter()
+        .map(|(stage, stats)| format!("\"{}\": {}", stage, stats.to_json()))
+        .collect::<Vec<_>>();
+      format!("    {{\"day\": \"{}\", {}}}", b.day, stages.join(", "))
+    })
+    .collect::<Vec<_>>();
+  format!("{{\n  \"iterations\": {},\n  \"days\": [\n{}\n  ]\n}}\n", iterations, days.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::days;
+
+  #[test]
+  fn test_stats()
+  {
+    let ms = Duration::from_millis;
+    assert_eq!(Stats::from_timings(&[]), None);
+    assert_eq!(Stats::from_timings(&[ms(3), ms(1), ms(2)]), Some(Stats { min: ms(1), median: ms(2), max: ms(3) }));
+    assert_eq!(Stats::from_timings(&[ms(4), ms(1), ms(2), ms(8)]), Some(Stats { min: ms(1), median: ms(3), max: ms(8) }));
+  }
+
+  #[test]
+  fn test_bench()
+  {
+    let day = days::find("1").unwrap();
+    let benchmark = bench(&day, "1721\n979\n366\n299\n675\n1456", 3).unwrap();
+    assert_eq!(benchmark.day, "1");
+    assert_eq!(benchmark.stages.iter().map(|(s, _)| *s).collect::<Vec<_>>(), vec!["parse", "part1", "part2"]);
+  }
+
+  #[test]
+  fn test_to_json()
+  {
+    let stats = Stats { min: Duration::from_nanos(1), median: Duration::from_nanos(2), max: Duration::from_nanos(3) };
+    let benchmarks = vec![
+      Benchmark { day: "15-dict".to_string(), stages: vec![("parse", stats), ("part1", stats)] },
+    ];
+    assert_eq!(to_json(&benchmarks, 5), r#"{
+  "iterations": 5,
+  "days": [
+    {"day": "15-dict", "parse": {"min_ns": 1, "median_ns": 2, "max_ns": 3}, "part1": {"min_ns": 1, "median_ns": 2, "max_ns": 3}}
+  ]
+}
+"#);
+  }
+}
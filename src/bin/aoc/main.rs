@@ -0,0 +1,652 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process;
+use AoC2020::bench;
+use AoC2020::days::{self, Day};
+use AoC2020::days::day1::{self, Day1, Expense};
+use AoC2020::days::day2::{self, Day2, PasswordPolicy};
+use AoC2020::days::day3::{self, Day3};
+use AoC2020::days::day7::BagRules;
+use AoC2020::error::AocError;
+use AoC2020::graph::Direction;
+use AoC2020::inputs::{InputStore, DEFAULT_NAME};
+use AoC2020::utils::{read_input, read_numbers_from_lines};
+use AoC2020::vm::{Instruction, LoopDetection, Machine};
+use AoC2020::vm::asm::assemble;
+use AoC2020::vm::debugger::Debugger;
+use AoC2020::vm::trace::{self, Trace};
+use AoC2020::Solution;
+
+const USAGE: &str = "Usage:
+  aoc run <day> [--part 1|2] [INPUT]
+  aoc bench [<day>...] [--iterations N] [--json PATH] [INPUT]
+  aoc inputs <day> [--data-dir DIR]
+  aoc expenses [--target N] [-k K] [--all] [INPUT]
+  aoc passwords [--policy NAME] [--report] [INPUT]
+  aoc slopes [--max DX,DY] [--draw DX,DY] [INPUT]
+  aoc debug 8 [INPUT]
+  aoc trace record <PATH> [--patch N] [INPUT]
+  aoc trace replay <PATH>
+  aoc trace diff <PATH> <PATH>
+  aoc bags dot <PATH> [--highlight BAG] [--up|--down] [INPUT]
+  aoc bags ancestors|contents <BAG> [INPUT]
+  aoc bags path <OUTER> <INNER> [INPUT]
+  aoc bags roots [INPUT]
+  aoc list
+
+Inputs are read from <data dir>/<day>-<name>.txt, where INPUT can be:
+  --name NAME      Use a named input, e.g. \"example\" for 17-example.txt (default: 1)
+  --data-dir DIR   Look for inputs in DIR (default: $AOC_DATA_DIR, or ./data)
+  --input PATH|-   Read the input from PATH, or from stdin
+
+expenses finds K entries of day 1's expense report that sum to N (default: 2 entries summing to
+2020), or every combination of them with --all.
+
+passwords counts the passwords in day 2's database that are valid under a policy, or lists every
+invalid one and why with --report. The policies are:
+  count              The letter appears between the first and second number of times (default)
+  positions          The letter is at exactly one of the two positions
+  none-of-positions  The letter is at neither of the two positions
+  at-most:N          The letter appears at most N times
+  regex:PATTERN      The password matches PATTERN
+
+slopes finds the slopes through day 3's forest that hit the fewest and the most trees, going at
+most DX left or right and DY down per step (default: 7,2), or draws the path down one slope with
+--draw, where a negative DX goes left.
+
+debug steps through day 8's boot code, with commands read from stdin. Type \"help\" for a list.
+
+trace records a run of day 8's boot code to PATH, optionally with the jmp or nop at address N
+flipped, and can check a recorded run or find where two of them went different ways.
+
+The bags commands work on day 7's rules:
+  dot              Write the rules to PATH as a Graphviz graph. --highlight marks BAG and every bag
+                   it can contain or be contained by, or only one of those with --down or --up
+  ancestors        List every bag that can contain BAG
+  contents         List every bag inside BAG, and how many of them there are in total
+  path             Show the shortest chain of bags from OUTER to INNER, and the one holding the most
+  roots            List the bags that aren't inside any other bag";
+
+/// Where to read a day's input from
+struct InputOptions
+{
+  data_dir: Option<PathBuf>,
+  name: String,
+  path: Option<PathBuf>,
+}
+
+impl InputOptions
+{
+  fn new() -> Self
+  {
+    InputOptions { data_dir: None, name: DEFAULT_NAME.to_string(), path: None }
+  }
+
+  /// Consume an input option and its value, returns false if the argument isn't an input option
+  fn parse_arg<'a, I>(&mut self, arg: &str, args: &mut I) -> Result<bool, String>
+    where I: Iterator<Item=&'a String>
+  {
+    match arg {
+      "--data-dir" => self.data_dir = Some(PathBuf::from(args.next().ok_or("--data-dir needs a directory")?)),
+      "--name" => self.name = args.next().ok_or("--name needs a name")?.to_string(),
+      "--input" => self.path = Some(PathBuf::from(args.next().ok_or("--input needs a path")?)),
+      _ => return Ok(false)
+    }
+    Ok(true)
+  }
+
+  fn store(&self) -> InputStore
+  {
+    match &self.data_dir {
+      Some(dir) => InputStore::new(dir),
+      None => InputStore::from_env()
+    }
+  }
+
+  fn read(&self, day: &Day) -> Result<String, AocError>
+  {
+    match &self.path {
+      Some(path) => Ok(read_input(path)?),
+      None => self.store().read(day.number, &self.name)
+    }
+  }
+}
+
+struct RunOptions
+{
+  day: Day,
+  parts: Vec<u8>,
+  input: InputOptions,
+}
+
+fn parse_run_options(args: &[String]) -> Result<RunOptions, String>
+{
+  let mut args = args.iter();
+  let name = args.next().ok_or("Missing day")?;
+  let day = days::find(name).ok_or(format!("Unknown day '{}'", name))?;
+  let mut parts = vec![1, 2];
+  let mut input = InputOptions::new();
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--part" => {
+        parts = match args.next().map(String::as_str) {
+          Some("1") => vec![1],
+          Some("2") => vec![2],
+          _ => return Err("--part must be 1 or 2".to_string())
+        }
+      }
+      _ => if !input.parse_arg(arg, &mut args)? {
+        return Err(format!("Unknown argument '{}'", arg));
+      }
+    }
+  }
+
+  Ok(RunOptions { day, parts, input })
+}
+
+struct BenchOptions
+{
+  days: Vec<Day>,
+  iterations: usize,
+  json: Option<PathBuf>,
+  input: InputOptions,
+}
+
+fn parse_bench_options(args: &[String]) -> Result<BenchOptions, String>
+{
+  let mut args = args.iter();
+  let mut days = vec![];
+  let mut iterations = 10;
+  let mut json = None;
+  let mut input = InputOptions::new();
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--iterations" => {
+        iterations = match args.next().map(|n| n.parse::<usize>()) {
+          Some(Ok(n)) if n > 0 => n,
+          _ => return Err("--iterations must be a positive number".to_string())
+        }
+      }
+      "--json" => json = Some(PathBuf::from(args.next().ok_or("--json needs a path")?)),
+      _ if input.parse_arg(arg, &mut args)? => (),
+      name => days.push(days::find(name).ok_or(format!("Unknown day '{}'", name))?)
+    }
+  }
+
+  if days.is_empty() {
+    days = days::all();
+  }
+  Ok(BenchOptions { days, iterations, json, input })
+}
+
+fn parse_inputs_options(args: &[String]) -> Result<(u8, InputOptions), String>
+{
+  let mut args = args.iter();
+  let day = args.next().and_then(|d| d.parse::<u8>().ok()).ok_or("Missing day number")?;
+  let mut input = InputOptions::new();
+
+  while let Some(arg) = args.next() {
+    if !input.parse_arg(arg, &mut args)? {
+      return Err(format!("Unknown argument '{}'", arg));
+    }
+  }
+
+  Ok((day, input))
+}
+
+enum BagsCommand
+{
+  Dot { path: PathBuf, highlight: Option<String>, directions: Vec<Direction> },
+  Ancestors { bag: String },
+  Contents { bag: String },
+  Path { outer: String, inner: String },
+  Roots,
+}
+
+fn parse_bags_options(args: &[String]) -> Result<(BagsCommand, InputOptions), String>
+{
+  let mut args = args.iter();
+  let mut input = InputOptions::new();
+  let bag = |args: &mut std::slice::Iter<String>| args.next().map(String::to_string).ok_or("Missing bag");
+
+  let mut command = match args.next().map(String::as_str) {
+    Some("dot") => {
+      let path = PathBuf::from(args.next().ok_or("Missing output path")?);
+      BagsCommand::Dot { path, highlight: None, directions: vec![Direction::Incoming, Direction::Outgoing] }
+    }
+    Some("ancestors") => BagsCommand::Ancestors { bag: bag(&mut args)? },
+    Some("contents") => BagsCommand::Contents { bag: bag(&mut args)? },
+    Some("path") => BagsCommand::Path { outer: bag(&mut args)?, inner: bag(&mut args)? },
+    Some("roots") => BagsCommand::Roots,
+    Some(command) => return Err(format!("Unknown bags command '{}'", command)),
+    None => return Err("Missing bags command".to_string())
+  };
+
+  while let Some(arg) = args.next() {
+    match (arg.as_str(), &mut command) {
+      ("--highlight", BagsCommand::Dot { highlight, .. }) => *highlight = Some(bag(&mut args)?),
+      ("--up", BagsCommand::Dot { directions, .. }) => *directions = vec![Direction::Incoming],
+      ("--down", BagsCommand::Dot { directions, .. }) => *directions = vec![Direction::Outgoing],
+      _ => if !input.parse_arg(arg, &mut args)? {
+        return Err(format!("Unknown argument '{}'", arg));
+      }
+    }
+  }
+
+  Ok((command, input))
+}
+
+struct ExpensesOptions
+{
+  target: isize,
+  k: usize,
+  all: bool,
+  input: InputOptions,
+}
+
+fn parse_expenses_options(args: &[String]) -> Result<ExpensesOptions, String>
+{
+  let mut args = args.iter();
+  let mut options = ExpensesOptions { target: 2020, k: 2, all: false, input: InputOptions::new() };
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--target" => options.target = args.next().and_then(|n| n.parse().ok()).ok_or("--target must be a number")?,
+      "-k" => options.k = args.next().and_then(|n| n.parse().ok()).ok_or("-k must be a number")?,
+      "--all" => options.all = true,
+      _ => if !options.input.parse_arg(arg, &mut args)? {
+        return Err(format!("Unknown argument '{}'", arg));
+      }
+    }
+  }
+
+  Ok(options)
+}
+
+struct PasswordsOptions
+{
+  policy: Box<dyn PasswordPolicy>,
+  report: bool,
+  input: InputOptions,
+}
+
+fn parse_passwords_options(args: &[String]) -> Result<PasswordsOptions, String>
+{
+  let mut args = args.iter();
+  let mut options = PasswordsOptions { policy: Box::new(day2::CountInRange), report: false, input: InputOptions::new() };
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--policy" => options.policy = day2::policy(args.next().ok_or("--policy needs a name")?)?,
+      "--report" => options.report = true,
+      _ => if !options.input.parse_arg(arg, &mut args)? {
+        return Err(format!("Unknown argument '{}'", arg));
+      }
+    }
+  }
+
+  Ok(options)
+}
+
+/// A slope written like "3,1", or "-3,1" going left
+fn parse_slope(s: Option<&String>) -> Option<(isize, usize)>
+{
+  let (x, y) = s?.split_once(',')?;
+  Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+struct SlopesOptions
+{
+  max: (isize, usize),
+  draw: Option<(isize, usize)>,
+  input: InputOptions,
+}
+
+fn parse_slopes_options(args: &[String]) -> Result<SlopesOptions, String>
+{
+  let mut args = args.iter();
+  let mut options = SlopesOptions { max: (7, 2), draw: None, input: InputOptions::new() };
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--max" => match parse_slope(args.next()) {
+        Some((x, y)) if x >= 0 => options.max = (x, y),
+        _ => return Err("--max needs a slope like 7,2".to_string())
+      },
+      "--draw" => match parse_slope(args.next()) {
+        Some((x, y)) if y > 0 => options.draw = Some((x, y)),
+        _ => return Err("--draw needs a slope like 3,1, going down".to_string())
+      },
+      _ => if !options.input.parse_arg(arg, &mut args)? {
+        return Err(format!("Unknown argument '{}'", arg));
+      }
+    }
+  }
+
+  Ok(options)
+}
+
+fn parse_debug_options(args: &[String]) -> Result<(Day, InputOptions), String>
+{
+  let mut args = args.iter();
+  let day = match args.next().map(String::as_str) {
+    Some("8") => days::find("8").expect("Day 8 is registered"),
+    Some(day) => return Err(format!("Can only debug day 8, not '{}'", day)),
+    None => return Err("Missing day".to_string())
+  };
+  let mut input = InputOptions::new();
+
+  while let Some(arg) = args.next() {
+    if !input.parse_arg(arg, &mut args)? {
+      return Err(format!("Unknown argument '{}'", arg));
+    }
+  }
+  if input.path.as_deref() == Some("-".as_ref()) {
+    return Err("The debugger reads its commands from stdin, so it can't read the input from there".to_string());
+  }
+
+  Ok((day, input))
+}
+
+enum TraceCommand
+{
+  Record { path: PathBuf, patch: Option<usize>, input: InputOptions },
+  Replay { path: PathBuf },
+  Diff { left: PathBuf, right: PathBuf },
+}
+
+fn parse_trace_options(args: &[String]) -> Result<TraceCommand, String>
+{
+  let mut args = args.iter();
+  let path = |args: &mut std::slice::Iter<String>| args.next().map(PathBuf::from).ok_or("Missing trace path");
+
+  let command = match args.next().map(String::as_str) {
+    Some("record") => {
+      let path = path(&mut args)?;
+      let mut patch = None;
+      let mut input = InputOptions::new();
+      while let Some(arg) = args.next() {
+        match arg.as_str() {
+          "--patch" => patch = Some(args.next().and_then(|n| n.parse().ok()).ok_or("--patch needs an address")?),
+          _ => if !input.parse_arg(arg, &mut args)? {
+            return Err(format!("Unknown argument '{}'", arg));
+          }
+        }
+      }
+      TraceCommand::Record { path, patch, input }
+    }
+    Some("replay") => TraceCommand::Replay { path: path(&mut args)? },
+    Some("diff") => TraceCommand::Diff { left: path(&mut args)?, right: path(&mut args)? },
+    Some(command) => return Err(format!("Unknown trace command '{}'", command)),
+    None => return Err("Missing trace command".to_string())
+  };
+
+  match args.next() {
+    Some(arg) => Err(format!("Unknown argument '{}'", arg)),
+    None => Ok(command)
+  }
+}
+
+fn run(options: RunOptions) -> Result<(), AocError>
+{
+  let RunOptions { day, parts, input } = options;
+  let input = input.read(&day)?;
+
+  let run = day.run(&input, &parts)?;
+  println!("Day {} parsed in {:?}", day.name(), run.parse_time);
+  for answer in run.answers {
+    println!("Day {} problem {}: {} ({:?})", day.name(), answer.part, answer.value, answer.time);
+  }
+
+  Ok(())
+}
+
+fn bench(options: BenchOptions) -> Result<(), AocError>
+{
+  let mut benchmarks = vec![];
+  for day in &options.days {
+    let input = options.input.read(day)?;
+    eprintln!("Benchmarking day {}...", day.name());
+    benchmarks.push(bench::bench(day, &input, options.iterations)?);
+  }
+
+  print!("{}", bench::to_table(&benchmarks));
+  if let Some(path) = options.json {
+    fs::write(path, bench::to_json(&benchmarks, options.iterations))?;
+  }
+
+  Ok(())
+}
+
+fn inputs((day, input): (u8, InputOptions)) -> Result<(), AocError>
+{
+  let store = input.store();
+  for name in store.names(day)? {
+    println!("{}\t{}", name, store.path(day, &name).display());
+  }
+
+  Ok(())
+}
+
+fn expenses(options: ExpensesOptions) -> Result<(), AocError>
+{
+  let day = days::find("1").expect("Day 1 is registered");
+  let input = options.input.read(&day)?;
+
+  let combinations = match options.all {
+    true => {
+      let entries = Day1::parse(&input)?;
+      day1::find_k_sum(&entries, options.k, options.target).into_iter()
+        .map(|indices| indices.into_iter().map(|i| Expense { line: i + 1, value: entries[i] }).collect())
+        .collect()
+    }
+    false => day1::find_expenses(read_numbers_from_lines(input.as_bytes()), options.k, options.target)?.into_iter().collect::<Vec<_>>()
+  };
+  if combinations.is_empty() {
+    return Err(AocError::NoSolution);
+  }
+  for expenses in combinations {
+    let sum = expenses.iter().map(|e| e.value.to_string()).collect::<Vec<_>>().join(" + ");
+    let lines = expenses.iter().map(|e| e.line.to_string()).collect::<Vec<_>>().join(", ");
+    println!("{} = {} (lines {}), product {}", sum, options.target, lines, expenses.iter().map(|e| e.value).product::<isize>());
+  }
+
+  Ok(())
+}
+
+fn passwords(options: PasswordsOptions) -> Result<(), AocError>
+{
+  let day = days::find("2").expect("Day 2 is registered");
+  let entries = Day2::parse(&options.input.read(&day)?)?;
+
+  if options.report {
+    for (i, entry) in entries.iter().enumerate() {
+      if let Err(reason) = options.policy.check(entry) {
+        println!("{}: {}: {}", i + 1, entry, reason);
+      }
+    }
+  } else {
+    println!("{} of {} passwords are valid", day2::count_valid(&entries, options.policy.as_ref()), entries.len());
+  }
+
+  Ok(())
+}
+
+/// Describe a slope like "going left 3, down 1"
+fn describe_slope(x: isize, y: usize) -> String
+{
+  let direction = if x < 0 { "left" } else { "right" };
+  format!("going {} {}, down {}", direction, x.abs(), y)
+}
+
+fn slopes(options: SlopesOptions) -> Result<(), AocError>
+{
+  let day = days::find("3").expect("Day 3 is registered");
+  let forest = Day3::parse(&options.input.read(&day)?)?;
+
+  if let Some((x, y)) = options.draw {
+    let not_down = || AocError::invalid_input("The slope doesn't go down");
+    println!("{}", day3::render_path(&forest, x, y).ok_or_else(not_down)?);
+    println!("{} trees {}", day3::trees_for_slope(&forest, x, y).ok_or_else(not_down)?, describe_slope(x, y));
+    return Ok(());
+  }
+
+  let (max_x, max_y) = options.max;
+  let (fewest, most) = day3::extreme_slopes(&forest, max_x as usize, max_y)
+    .ok_or_else(|| AocError::invalid_input("There are no slopes going down within the bounds"))?;
+  println!("Fewest trees: {} {}", fewest.trees, describe_slope(fewest.delta_x, fewest.delta_y));
+  println!("Most trees: {} {}", most.trees, describe_slope(most.delta_x, most.delta_y));
+
+  Ok(())
+}
+
+fn debug((day, input): (Day, InputOptions)) -> Result<(), AocError>
+{
+  let program = assemble(&input.read(&day)?)?;
+  let mut debugger = Debugger::new(Machine::new(program).halt_on(LoopDetection::new()));
+  println!("{}", debugger.execute("print").unwrap_or_default());
+
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+  loop {
+    print!("(debug) ");
+    io::stdout().flush()?;
+    let line = match lines.next() {
+      Some(line) => line?,
+      None => break
+    };
+    match line.trim() {
+      "quit" | "q" => break,
+      command => match debugger.execute(command) {
+        Ok(output) if output.is_empty() => (),
+        Ok(output) => println!("{}", output),
+        Err(e) => println!("Error: {}", e)
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn trace(command: TraceCommand) -> Result<(), AocError>
+{
+  let read_trace = |path: &PathBuf| -> Result<Trace, AocError> { Trace::parse(&read_input(path)?) };
+
+  match command {
+    TraceCommand::Record { path, patch, input } => {
+      let day = days::find("8").expect("Day 8 is registered");
+      let mut program = assemble(&input.read(&day)?)?;
+      if let Some(address) = patch {
+        let flipped = program.get(address).copied().and_then(Instruction::flipped)
+          .ok_or_else(|| AocError::invalid_input(format!("There is no jmp or nop to flip at address {}", address)))?;
+        program[address] = flipped;
+      }
+
+      let mut machine = Machine::new(program).halt_on(LoopDetection::new()).record_trace();
+      let halt = machine.run();
+      println!("Halted after {} steps with acc {}: {}", machine.steps(), machine.state().acc(), halt);
+      fs::write(path, machine.trace().map(Trace::to_string).unwrap_or_default())?;
+    }
+    TraceCommand::Replay { path } => {
+      let trace = read_trace(&path)?;
+      let state = trace.replay().map_err(AocError::InvalidInput)?;
+      for entry in &trace.entries {
+        println!("{:>6}: {:<10} acc {} -> {}", entry.pc, entry.instruction.to_string(), entry.acc_before, entry.acc_after);
+      }
+      match trace.halt {
+        Some(halt) => println!("Halted after {} steps with acc {}: {}", trace.entries.len(), state.acc(), halt),
+        None => println!("Still running after {} steps with acc {}", trace.entries.len(), state.acc())
+      }
+    }
+    TraceCommand::Diff { left, right } => {
+      match trace::diff(&read_trace(&left)?, &read_trace(&right)?) {
+        Some(divergence) => println!("{}", divergence),
+        None => println!("The runs are identical")
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn bags((command, input): (BagsCommand, InputOptions)) -> Result<(), AocError>
+{
+  let day = days::find("7").expect("Day 7 is registered");
+  let rules = BagRules::parse(&input.read(&day)?)?;
+
+  match command {
+    BagsCommand::Dot { path, highlight, directions } => {
+      let highlight = highlight.as_deref().map(|bag| (bag, directions.as_slice()));
+      fs::write(path, rules.to_dot(highlight)?)?;
+    }
+    BagsCommand::Ancestors { bag } => {
+      for ancestor in rules.ancestors(&bag)? {
+        println!("{}", ancestor);
+      }
+    }
+    BagsCommand::Contents { bag } => {
+      for (inner, count) in rules.contents(&bag)? {
+        println!("{}\t{}", count, inner);
+      }
+    }
+    BagsCommand::Path { outer, inner } => {
+      match (rules.shortest_path(&outer, &inner)?, rules.heaviest_path(&outer, &inner)?) {
+        (Some(shortest), Some((heaviest, count))) => {
+          println!("Shortest: {}", shortest.join(" -> "));
+          println!("Heaviest: {} ({} {} bags)", heaviest.join(" -> "), count, inner);
+        }
+        _ => println!("{} bags can't contain {} bags", outer, inner)
+      }
+    }
+    BagsCommand::Roots => {
+      for root in rules.roots() {
+        println!("{}", root);
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn list() -> Result<(), AocError>
+{
+  for day in days::all() {
+    println!("{}", day.name());
+  }
+
+  Ok(())
+}
+
+fn main() {
+  let args: Vec<String> = env::args().skip(1).collect();
+
+  let result = match args.first().map(String::as_str) {
+    Some("run") => parse_run_options(&args[1..]).map(run),
+    Some("bench") => parse_bench_options(&args[1..]).map(bench),
+    Some("inputs") => parse_inputs_options(&args[1..]).map(inputs),
+    Some("expenses") => parse_expenses_options(&args[1..]).map(expenses),
+    Some("passwords") => parse_passwords_options(&args[1..]).map(passwords),
+    Some("slopes") => parse_slopes_options(&args[1..]).map(slopes),
+    Some("debug") => parse_debug_options(&args[1..]).map(debug),
+    Some("trace") => parse_trace_options(&args[1..]).map(trace),
+    Some("bags") => parse_bags_options(&args[1..]).map(bags),
+    Some("list") => Ok(list()),
+    _ => Err("Missing command".to_string())
+  };
+
+  match result {
+    Ok(Ok(())) => (),
+    Ok(Err(e)) => {
+      eprintln!("Error: {}", e);
+      process::exit(1);
+    }
+    Err(e) => {
+      eprintln!("{}\n\n{}", e, USAGE);
+      process::exit(1);
+    }
+  }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use crate::error::AocError;
+use crate::utils::read_numbers_from_lines;
+use crate::Solution;
+
+/// Visit every combination of `k` entries of `sorted` (values paired with their original indices,
+/// sorted by value) that sums to `target`. Stops as soon as `found` returns true, and returns
+/// whether it did.
+fn visit_k_sums<F>(sorted: &[(isize, usize)], k: usize, target: isize, chosen: &mut Vec<usize>, found: &mut F) -> bool
+  where F: FnMut(&[usize]) -> bool
+{
+  match k {
+    0 => target == 0 && found(chosen),
+    1 => sorted.iter().filter(|(value, _)| *value == target).any(|&(_, index)| {
+      chosen.push(index);
+      let stop = found(chosen);
+      chosen.pop();
+      stop
+    }),
+    2 => {
+      let mut emit = |i: usize, j: usize| {
+        chosen.push(sorted[i].1);
+        chosen.push(sorted[j].1);
+        let stop = found(chosen);
+        chosen.truncate(chosen.len() - 2);
+        stop
+      };
+      let (mut low, mut high) = (0, sorted.len().saturating_sub(1));
+      while low < high {
+        let (low_value, high_value) = (sorted[low].0, sorted[high].0);
+        if low_value + high_value < target {
+          low += 1;
+        } else if low_value + high_value > target {
+          high -= 1;
+        } else if low_value == high_value {
+          // Everything in between is the same value, so any two of them will do
+          return (low..=high).any(|i| (i + 1..=high).any(|j| emit(i, j)));
+        } else {
+          // Every entry with the low value pairs up with every entry with the high value
+          let low_end = low + sorted[low..].iter().take_while(|(v, _)| *v == low_value).count();
+          let high_start = high + 1 - sorted[..=high].iter().rev().take_while(|(v, _)| *v == high_value).count();
+          if (low..low_end).any(|i| (high_start..=high).any(|j| emit(i, j))) {
+            return true;
+          }
+          low = low_end;
+          high = high_start - 1;
+        }
+      }
+      false
+    }
+    _ => (0..sorted.len()).any(|i| {
+      chosen.push(sorted[i].1);
+      let stop = visit_k_sums(&sorted[i + 1..], k - 1, target - sorted[i].0, chosen, found);
+      chosen.pop();
+      stop
+    })
+  }
+}
+
+/// Sort the numbers, keeping track of where they came from
+fn sorted_with_indices(numbers: &[isize]) -> Vec<(isize, usize)>
+{
+  let mut sorted = numbers.iter().copied().zip(0..).collect::<Vec<_>>();
+  sorted.sort_unstable();
+  sorted
+}
+
+/// Find every combination of `k` different entries that sums to `target`, as the entries' indices in
+/// increasing order.
+///
+/// Pairs are found by walking inwards from both ends of the sorted numbers, and bigger combinations
+/// by picking each number in turn and looking for `k - 1` more after it.
+pub fn find_k_sum(numbers: &[isize], k: usize, target: isize) -> Vec<Vec<usize>>
+{
+  let mut combinations = vec![];
+  visit_k_sums(&sorted_with_indices(numbers), k, target, &mut vec![], &mut |indices: &[usize]| {
+    let mut indices = indices.to_vec();
+    indices.sort_unstable();
+    combinations.push(indices);
+    false
+  });
+  combinations.sort();
+  combinations
+}
+
+/// Find a combination of `k` different entries that sums to `target`, like `find_k_sum`, but stop at
+/// the first one.
+pub fn find_first_k_sum(numbers: &[isize], k: usize, target: isize) -> Option<Vec<usize>>
+{
+  let mut first = None;
+  visit_k_sums(&sorted_with_indices(numbers), k, target, &mut vec![], &mut |indices: &[usize]| {
+    let mut indices = indices.to_vec();
+    indices.sort_unstable();
+    first = Some(indices);
+    true
+  });
+  first
+}
+
+/// An entry of the expense report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Expense
+{
+  pub line: usize,
+  pub value: isize,
+}
+
+/// The entries read so far, along with where every value can be found, so that entries with the
+/// same value are all kept
+#[derive(Default)]
+struct Expenses
+{
+  entries: Vec<Expense>,
+  /// The positions of the entries with each value, in increasing order
+  by_value: HashMap<isize, Vec<usize>>,
+}
+
+impl Expenses
+{
+  fn insert(&mut self, expense: Expense)
+  {
+    self.by_value.entry(expense.value).or_default().push(self.entries.len());
+    self.entries.push(expense);
+  }
+
+  /// Find `k` different entries among the first `before` that sum to `target`, as their positions in
+  /// increasing order
+  fn find(&self, k: usize, target: isize, before: usize) -> Option<Vec<usize>>
+  {
+    match k {
+      0 => if target == 0 { Some(vec![]) } else { None },
+      1 => self.by_value.get(&target)?.first().filter(|&&p| p < before).map(|&p| vec![p]),
+      _ => (0..before).rev().find_map(|p| {
+        let mut found = self.find(k - 1, target - self.entries[p].value, p)?;
+        found.push(p);
+        Some(found)
+      })
+    }
+  }
+}
+
+/// Read numbers until `k` of them sum to `target`, and return those entries in the order they were
+/// read.
+///
+/// Every number is only checked against the ones read before it, so reading stops as soon as the
+/// last entry of the answer shows up. Entries with the same value count as different entries.
+pub fn find_expenses<I>(numbers: I, k: usize, target: isize) -> Result<Option<Vec<Expense>>, AocError>
+  where I: IntoIterator<Item=Result<isize, AocError>>
+{
+  if k == 0 {
+    return Ok(if target == 0 { Some(vec![]) } else { None });
+  }
+
+  let mut expenses = Expenses::default();
+  for (i, number) in numbers.into_iter().enumerate() {
+    let expense = Expense { line: i + 1, value: number? };
+    if let Some(found) = expenses.find(k - 1, target - expense.value, expenses.entries.len()) {
+      let mut found = found.into_iter().map(|p| expenses.entries[p]).collect::<Vec<_>>();
+      found.push(expense);
+      return Ok(Some(found));
+    }
+    expenses.insert(expense);
+  }
+  Ok(None)
+}
+
+/// The product of the `k` entries that sum to 2020
+fn product_of_k_sum(entries: &[isize], k: usize) -> Result<isize, AocError>
+{
+  let found = find_expenses(entries.iter().map(|&n| Ok(n)), k, 2020)?.ok_or(AocError::NoSolution)?;
+  Ok(found.iter().map(|e| e.value).product())
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+  type Input = Vec<isize>;
+  type Output1 = isize;
+  type Output2 = isize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    read_numbers_from_lines(input.as_bytes()).collect()
+  }
+
+  fn part1(input: &Self::Input) -> Result<isize, AocError>
+  {
+    product_of_k_sum(input, 2)
+  }
+
+  fn part2(input: &Self::Input) -> Result<isize, AocError>
+  {
+    product_of_k_sum(input, 3)
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn test_find_k_sum()
+  {
+    let numbers = [1721, 979, 366, 299, 675, 1456];
+    assert_eq!(find_k_sum(&numbers, 2, 2020), vec![vec![0, 3]]);
+    assert_eq!(find_k_sum(&numbers, 3, 2020), vec![vec![1, 2, 4]]);
+    assert_eq!(find_k_sum(&numbers, 1, 366), vec![vec![2]]);
+    assert_eq!(find_k_sum(&numbers, 2, 1), Vec::<Vec<usize>>::new());
+    assert_eq!(find_first_k_sum(&numbers, 3, 2020), Some(vec![1, 2, 4]));
+    assert_eq!(find_first_k_sum(&numbers, 4, 2020), None);
+  }
+
+  #[test]
+  fn test_find_k_sum_duplicates()
+  {
+    let numbers = [5, 5, 5, 0, -5, 10];
+    assert_eq!(find_k_sum(&numbers, 2, 10), vec![vec![0, 1], vec![0, 2], vec![1, 2], vec![3, 5]]);
+    assert_eq!(find_k_sum(&numbers, 2, 5), vec![vec![0, 3], vec![1, 3], vec![2, 3], vec![4, 5]]);
+    assert_eq!(find_k_sum(&numbers, 3, 15), vec![vec![0, 1, 2], vec![0, 3, 5], vec![1, 3, 5], vec![2, 3, 5]]);
+    assert_eq!(find_k_sum(&numbers, 6, 20), vec![vec![0, 1, 2, 3, 4, 5]]);
+  }
+
+  #[test]
+  fn test_find_expenses()
+  {
+    let numbers = |input: &'static str| read_numbers_from_lines(input.as_bytes());
+    let expense = |line, value| Expense { line, value };
+
+    let found = find_expenses(numbers("1721\n979\n366\n299\n675\n1456"), 3, 2020).unwrap();
+    assert_eq!(found, Some(vec![expense(2, 979), expense(3, 366), expense(5, 675)]));
+
+    // The same value twice, and negative entries
+    assert_eq!(find_expenses(numbers("1010\n5\n1010"), 2, 2020).unwrap(), Some(vec![expense(1, 1010), expense(3, 1010)]));
+    assert_eq!(find_expenses(numbers("1010\n5"), 2, 2020).unwrap(), None);
+    assert_eq!(find_expenses(numbers("-20\n2000\n40"), 3, 2020).unwrap(), Some(vec![expense(1, -20), expense(2, 2000), expense(3, 40)]));
+    assert_eq!(find_expenses(numbers("7\n-7\n7"), 3, 7).unwrap(), Some(vec![expense(1, 7), expense(2, -7), expense(3, 7)]));
+
+    // Reading stops at the answer, so a bad line after it doesn't matter
+    assert!(find_expenses(numbers("1000\n1020\nx"), 2, 2020).unwrap().is_some());
+    assert!(matches!(find_expenses(numbers("1000\nx\n1020"), 2, 2020), Err(AocError::Parse { line: 2, .. })));
+  }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use crate::error::AocError;
+use crate::utils::parse_lines;
+use crate::Solution;
+
+fn count_possibilities(list: &[usize]) -> usize
+{
+  let highest = list.last().unwrap();
+  let mut map = HashMap::<usize, usize>::with_capacity(list.len());
+  map.insert(0, 1);
+
+  for &e in list.iter().skip(1) {
+    let mut count = 0;
+    if e > 0 {
+      count += map.get(&(e - 1)).unwrap_or(&0usize);
+    }
+
+    if e > 1 {
+      count += map.get(&(e - 2)).unwrap_or(&0usize);
+    }
+
+    if e > 2 {
+      count += map.get(&(e - 3)).unwrap_or(&0usize);
+    }
+
+    map.insert(e, count);
+  }
+
+  map[highest]
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+  type Input = Vec<usize>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  /// The adapters sorted by joltage, including the outlet at the start and the device at the end
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    let mut numbers = parse_lines(input, |l| l.parse::<usize>())?;
+    numbers.push(0);
+    numbers.sort_unstable();
+    numbers.push(numbers.last().unwrap() + 3);
+    Ok(numbers)
+  }
+
+  fn part1(list: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut previous = list.last().unwrap();
+    let mut three_diff = 0;
+    let mut one_diff = 0;
+
+    for l in list.iter().rev().skip(1) {
+      match previous - l {
+        3 => three_diff += 1,
+        1 => one_diff += 1,
+        _ => ()
+      };
+      previous = l;
+    }
+    Ok(three_diff * one_diff)
+  }
+
+  fn part2(list: &Self::Input) -> Result<usize, AocError>
+  {
+    Ok(count_possibilities(list))
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::count_possibilities;
+
+  #[test]
+  fn test_count_possibilities()
+  {
+    let mut nums: Vec<usize> = vec![28, 33, 18, 42, 31, 14, 46, 20, 48, 47, 24, 23, 49, 45, 19, 38, 39, 11, 1, 32, 25, 35, 8, 17, 7, 9, 4, 2, 34, 10, 3, 0, 52];
+    nums.sort();
+
+    assert_eq!(count_possibilities(&nums), 19208);
+  }
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
-use std::path::Path;
-use AoC2020::utils::read_lines;
+use crate::error::AocError;
+use crate::grid::{Grid, ToChar, DIRECTIONS8};
+use crate::Solution;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 enum Seating
@@ -11,127 +12,87 @@ enum Seating
 
 impl Seating
 {
-  fn from_char(c: &char) -> Self
+  fn from_char(c: char) -> Result<Self, String>
   {
     match c {
-      '#' => Seating::Occupied,
-      'L' => Seating::Unoccupied,
-      _ => Seating::Floor
+      '#' => Ok(Seating::Occupied),
+      'L' => Ok(Seating::Unoccupied),
+      '.' => Ok(Seating::Floor),
+      _ => Err(format!("Invalid seat {:?}", c))
+    }
+  }
+}
+
+impl ToChar for Seating
+{
+  fn to_char(&self) -> char
+  {
+    match self {
+      Seating::Occupied => '#',
+      Seating::Unoccupied => 'L',
+      Seating::Floor => '.'
     }
   }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-struct SeatingArrangement
+pub struct SeatingArrangement
 {
-  seats: Vec<Vec<Seating>>
+  seats: Grid<Seating>
 }
 
 impl SeatingArrangement
 {
-  fn from_strings(strings: Vec<&str>) -> Option<Self>
+  fn parse(input: &str) -> Result<Self, AocError>
   {
-    let length = strings.get(0)?.len();
-    if !strings.iter().all(|s| s.len() == length)
-    {
-      None
-    } else {
-      Some(SeatingArrangement {
-        seats: strings.iter()
-          .map(|s|
-            s.chars()
-              .map(|c| Seating::from_char(&c))
-              .collect::<Vec<_>>())
-          .collect::<Vec<_>>()
-      })
-    }
+    Ok(SeatingArrangement { seats: Grid::parse(input, Seating::from_char)? })
   }
 
-  fn get_adjacent(&self, x: usize, y: usize) -> Vec<Vec<Option<Seating>>>
+  #[cfg(test)]
+  fn from_strings(strings: Vec<&str>) -> Option<Self>
   {
-    let left = (x as isize) - 1;
-    let top = (y as isize) - 1;
-    (top..top + 3)
-      .map(|y|
-        (left..left + 3)
-          .map(|x|
-            if x < 0 || y < 0 || x >= (self.width() as isize) || y >= (self.height() as isize) {
-              None
-            } else {
-              Some(self.seats[y as usize][x as usize].clone())
-            })
-          .collect::<Vec<_>>())
-      .collect::<Vec<_>>()
+    Self::parse(&strings.join("\n")).ok()
   }
 
-  fn is_seat_in_los(&self, x: usize, y: usize, delta: &(isize, isize)) -> bool
+  fn count_adjacent(&self, x: usize, y: usize) -> usize
   {
-    let x_range = 0isize..(self.width() as isize);
-    let y_range = 0isize..(self.height() as isize);
-    let mut i_x = (x as isize) + delta.0;
-    let mut i_y = (y as isize) + delta.1;
-
-    while x_range.contains(&i_x) && y_range.contains(&i_y) {
-      match self.get_seat(i_x as usize, i_y as usize) {
-        Seating::Occupied => return true,
-        Seating::Unoccupied => return false,
-        _ => ()
-      }
-
-      i_x = i_x + delta.0;
-      i_y = i_y + delta.1;
-    }
+    self.seats.neighbours8(x, y)
+      .filter(|(_, &s)| s == Seating::Occupied)
+      .count()
+  }
 
-    false
+  fn is_seat_in_los(&self, x: usize, y: usize, delta: (isize, isize)) -> bool
+  {
+    self.seats.ray(x, y, delta)
+      .map(|(_, &s)| s)
+      .find(|&s| s != Seating::Floor)
+      == Some(Seating::Occupied)
   }
 
   fn count_in_los(&self, x: usize, y: usize) -> usize
   {
-    let deltas = vec![
-      (-1, -1), (0, -1), (1, -1),
-      (-1, 0), (1, 0),
-      (-1, 1), (0, 1), (1, 1)
-    ];
-
-    deltas.into_iter()
-      .filter(|d| self.is_seat_in_los(x, y, d))
+    DIRECTIONS8.iter()
+      .filter(|&&d| self.is_seat_in_los(x, y, d))
       .count()
   }
 
-  fn width(&self) -> usize
+  fn get_seat(&self, x: usize, y: usize) -> Seating
   {
-    self.seats[0].len()
+    self.seats[(x, y)]
   }
 
-  fn height(&self) -> usize
+  fn count_occupied(&self) -> usize
   {
-    self.seats.len()
-  }
-
-  fn get_seat(&self, x: usize, y: usize) -> Seating
-  {
-    self.seats[y][x]
+    self.seats.iter().filter(|&s| *s == Seating::Occupied).count()
   }
 }
 
 fn next_seating_adjacent(seats: &SeatingArrangement, x: usize, y: usize) -> Seating
 {
-  let section = seats.get_adjacent(x, y);
-  let num_occupied: usize = section
-    .iter()
-    .flatten()
-    .enumerate()
-    .map(|s| match s {
-      (i, &Some(Seating::Occupied)) if i != 4 => 1,
-      _ => 0
-    })
-    .sum();
-
-  match section[1][1] {
-    Some(Seating::Occupied) if num_occupied >= 4 => Seating::Unoccupied,
-    Some(Seating::Unoccupied) if num_occupied == 0 => Seating::Occupied,
-    Some(x) => x,
-    _ => Seating::Floor
+  match (seats.get_seat(x, y), seats.count_adjacent(x, y)) {
+    (Seating::Occupied, num) if num >= 4 => Seating::Unoccupied,
+    (Seating::Unoccupied, 0) => Seating::Occupied,
+    (seat, _) => seat
   }
 }
 
@@ -148,15 +109,12 @@ fn do_step<F>(seats: &SeatingArrangement, f: F) -> SeatingArrangement
   where F: Fn(&SeatingArrangement, usize, usize) -> Seating
 {
   SeatingArrangement {
-    seats: (0..seats.height()).map(|y|
-      (0..seats.width())
-        .map(|x| f(&seats, x, y))
-        .collect::<Vec<_>>()
-    ).collect::<Vec<_>>()
+    seats: seats.seats.map(|(x, y), _| f(seats, x, y))
   }
 }
 
-fn run_to_completion<F>(seats: &SeatingArrangement, f: F) -> SeatingArrangement
+/// Apply the rules until the seats stop changing, or fail if they haven't settled after 200 rounds
+fn run_to_completion<F>(seats: &SeatingArrangement, f: F) -> Result<SeatingArrangement, AocError>
   where F: Fn(&SeatingArrangement, usize, usize) -> Seating
 {
   let mut copy = (*seats).clone();
@@ -164,7 +122,7 @@ fn run_to_completion<F>(seats: &SeatingArrangement, f: F) -> SeatingArrangement
 
   loop {
     if iterations > 200 {
-      panic!("Too many iterations");
+      return Err(AocError::NoSolution);
     }
     let next = do_step(&copy, &f);
     if next == copy {
@@ -174,37 +132,30 @@ fn run_to_completion<F>(seats: &SeatingArrangement, f: F) -> SeatingArrangement
     iterations += 1
   }
 
-  copy
+  Ok(copy)
 }
 
-fn problem1(path: &Path) -> usize
-{
-  let strings = read_lines(path).unwrap().map(|s| s.unwrap()).collect::<Vec<String>>();
-  let arrangement = SeatingArrangement::from_strings(strings.iter().map(AsRef::as_ref).collect()).unwrap();
-  run_to_completion(&arrangement, next_seating_adjacent)
-    .seats
-    .iter()
-    .flatten()
-    .filter(|&s| *s == Seating::Occupied)
-    .count()
-}
+pub struct Day11;
 
-fn problem2(path: &Path) -> usize
-{
-  let strings = read_lines(path).unwrap().map(|s| s.unwrap()).collect::<Vec<String>>();
-  let arrangement = SeatingArrangement::from_strings(strings.iter().map(AsRef::as_ref).collect()).unwrap();
-  run_to_completion(&arrangement, next_seating_los)
-    .seats
-    .iter()
-    .flatten()
-    .filter(|&s| *s == Seating::Occupied)
-    .count()
-}
+impl Solution for Day11 {
+  type Input = SeatingArrangement;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    SeatingArrangement::parse(input)
+  }
+
+  fn part1(arrangement: &SeatingArrangement) -> Result<usize, AocError>
+  {
+    Ok(run_to_completion(arrangement, next_seating_adjacent)?.count_occupied())
+  }
 
-fn main() {
-  let path = Path::new(r"data/11-1.txt");
-  println!("Result of problem 1: {}", problem1(path));
-  println!("Result of problem 2: {}", problem2(path));
+  fn part2(arrangement: &SeatingArrangement) -> Result<usize, AocError>
+  {
+    Ok(run_to_completion(arrangement, next_seating_los)?.count_occupied())
+  }
 }
 
 #[cfg(test)]
@@ -225,17 +176,19 @@ mod tests
     );
 
     assert_eq!(arrangement, Some(SeatingArrangement {
-      seats: vec![
-        vec![Seating::Unoccupied, Seating::Occupied, Seating::Floor, Seating::Unoccupied],
-        vec![Seating::Floor, Seating::Floor, Seating::Floor, Seating::Floor],
-        vec![Seating::Unoccupied, Seating::Occupied, Seating::Occupied, Seating::Unoccupied],
-        vec![Seating::Floor, Seating::Occupied, Seating::Occupied, Seating::Floor]
-      ]
+      seats: Grid::new(4, 4, vec![
+        Seating::Unoccupied, Seating::Occupied, Seating::Floor, Seating::Unoccupied,
+        Seating::Floor, Seating::Floor, Seating::Floor, Seating::Floor,
+        Seating::Unoccupied, Seating::Occupied, Seating::Occupied, Seating::Unoccupied,
+        Seating::Floor, Seating::Occupied, Seating::Occupied, Seating::Floor
+      ]).unwrap()
     }));
+    assert_eq!(arrangement.unwrap().seats.to_string(), "L#.L\n....\nL##L\n.##.");
+    assert_eq!(SeatingArrangement::from_strings(vec!["L#", "L"]), None);
   }
 
   #[test]
-  fn test_get_adjacent()
+  fn test_count_adjacent()
   {
     let arrangement = SeatingArrangement::from_strings(
       vec![
@@ -246,21 +199,10 @@ mod tests
       ]
     ).unwrap();
 
-    assert_eq!(arrangement.get_adjacent(0, 0), vec![
-      vec![None, None, None],
-      vec![None, Some(Seating::Unoccupied), Some(Seating::Occupied)],
-      vec![None, Some(Seating::Floor), Some(Seating::Floor)]
-    ]);
-    assert_eq!(arrangement.get_adjacent(3, 3), vec![
-      vec![Some(Seating::Occupied), Some(Seating::Unoccupied), None],
-      vec![Some(Seating::Occupied), Some(Seating::Floor), None],
-      vec![None, None, None]
-    ]);
-    assert_eq!(arrangement.get_adjacent(1, 1), vec![
-      vec![Some(Seating::Unoccupied), Some(Seating::Occupied), Some(Seating::Floor)],
-      vec![Some(Seating::Floor), Some(Seating::Floor), Some(Seating::Floor)],
-      vec![Some(Seating::Unoccupied), Some(Seating::Occupied), Some(Seating::Occupied)]
-    ]);
+    assert_eq!(arrangement.count_adjacent(0, 0), 1);
+    assert_eq!(arrangement.count_adjacent(3, 3), 2);
+    assert_eq!(arrangement.count_adjacent(1, 1), 3);
+    assert_eq!(arrangement.count_adjacent(1, 2), 3);
   }
 
   #[test]
@@ -429,7 +371,7 @@ mod tests
       ]
     ).unwrap();
 
-    assert_eq!(run_to_completion(&arrangement, next_seating_adjacent), finished_arrangement);
+    assert_eq!(run_to_completion(&arrangement, next_seating_adjacent).unwrap(), finished_arrangement);
   }
 
   #[test]
@@ -465,6 +407,6 @@ mod tests
       ]
     ).unwrap();
 
-    assert_eq!(run_to_completion(&arrangement, next_seating_los), finished_arrangement);
+    assert_eq!(run_to_completion(&arrangement, next_seating_los).unwrap(), finished_arrangement);
   }
 }
\ No newline at end of file
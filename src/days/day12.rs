@@ -1,5 +1,6 @@
-use std::path::Path;
-use AoC2020::utils::read_lines;
+use crate::error::AocError;
+use crate::utils::parse_lines;
+use crate::Solution;
 
 fn heading_to_delta(heading: isize) -> (isize, isize)
 {
@@ -19,8 +20,8 @@ fn heading_to_delta(heading: isize) -> (isize, isize)
   )
 }
 
-#[derive(Debug, Eq, PartialEq)]
-enum Instruction
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Instruction
 {
   North(usize),
   East(usize),
@@ -124,54 +125,60 @@ impl Waypoint
   }
 }
 
-
-fn parse_instruction(instruction: &str) -> Instruction
+fn parse_instruction(instruction: &str) -> Result<Instruction, String>
 {
-  let c = instruction.chars().nth(0).unwrap();
-  let n = instruction.chars().skip(1).collect::<String>().parse::<usize>().unwrap_or(0);
+  let c = instruction.chars().next().ok_or("Empty instruction")?;
+  let n = instruction[c.len_utf8()..].parse::<usize>().map_err(|e| format!("Invalid amount: {}", e))?;
 
   match c {
-    'L' => Instruction::Left(n),
-    'R' => Instruction::Right(n),
-    'F' => Instruction::Forward(n),
-    'N' => Instruction::North(n),
-    'E' => Instruction::East(n),
-    'S' => Instruction::South(n),
-    'W' => Instruction::West(n),
-    _ => panic!()
+    'L' => Ok(Instruction::Left(n)),
+    'R' => Ok(Instruction::Right(n)),
+    'F' => Ok(Instruction::Forward(n)),
+    'N' => Ok(Instruction::North(n)),
+    'E' => Ok(Instruction::East(n)),
+    'S' => Ok(Instruction::South(n)),
+    'W' => Ok(Instruction::West(n)),
+    _ => Err(format!("Invalid action {:?}", c))
   }
 }
 
-fn problem1(path: &Path) -> usize
-{
-  let mut ship = Ship::new();
-  for i in read_lines(path).unwrap().map(|s| parse_instruction(&s.unwrap()))
+pub struct Day12;
+
+impl Solution for Day12 {
+  type Input = Vec<Instruction>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
   {
-    ship.follow_instruction(i);
+    parse_lines(input, parse_instruction)
   }
 
-  (ship.x.abs() + ship.y.abs()) as usize
-}
-
-fn problem2(path: &Path) -> usize
-{
-  let mut waypoint = Waypoint::new(10, 1);
-  let mut ship = Ship::new();
-  for i in read_lines(path).unwrap().map(|s| parse_instruction(&s.unwrap()))
+  fn part1(instructions: &Self::Input) -> Result<usize, AocError>
   {
-    match i {
-      Instruction::Forward(n) => ship.drive_to_waypoint(&waypoint, n),
-      i => waypoint.follow_instruction(i)
+    let mut ship = Ship::new();
+    for &i in instructions
+    {
+      ship.follow_instruction(i);
     }
+
+    Ok((ship.x.abs() + ship.y.abs()) as usize)
   }
 
-  (ship.x.abs() + ship.y.abs()) as usize
-}
+  fn part2(instructions: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut waypoint = Waypoint::new(10, 1);
+    let mut ship = Ship::new();
+    for &i in instructions
+    {
+      match i {
+        Instruction::Forward(n) => ship.drive_to_waypoint(&waypoint, n),
+        i => waypoint.follow_instruction(i)
+      }
+    }
 
-fn main() {
-  let path = Path::new(r"data/12-1.txt");
-  println!("Result of problem 1: {}", problem1(path));
-  println!("Result of problem 2: {}", problem2(path));
+    Ok((ship.x.abs() + ship.y.abs()) as usize)
+  }
 }
 
 #[cfg(test)]
@@ -224,13 +231,16 @@ mod tests
   #[test]
   fn test_parse_instruction()
   {
-    assert_eq!(parse_instruction("L10"), Instruction::Left(10));
-    assert_eq!(parse_instruction("R5"), Instruction::Right(5));
-    assert_eq!(parse_instruction("F15"), Instruction::Forward(15));
-    assert_eq!(parse_instruction("E4"), Instruction::East(4));
-    assert_eq!(parse_instruction("W20"), Instruction::West(20));
-    assert_eq!(parse_instruction("S25"), Instruction::South(25));
-    assert_eq!(parse_instruction("N22"), Instruction::North(22));
+    assert_eq!(parse_instruction("L10"), Ok(Instruction::Left(10)));
+    assert_eq!(parse_instruction("R5"), Ok(Instruction::Right(5)));
+    assert_eq!(parse_instruction("F15"), Ok(Instruction::Forward(15)));
+    assert_eq!(parse_instruction("E4"), Ok(Instruction::East(4)));
+    assert_eq!(parse_instruction("W20"), Ok(Instruction::West(20)));
+    assert_eq!(parse_instruction("S25"), Ok(Instruction::South(25)));
+    assert_eq!(parse_instruction("N22"), Ok(Instruction::North(22)));
+
+    assert!(parse_instruction("X10").is_err());
+    assert!(parse_instruction("F").is_err());
   }
 
   #[test]
@@ -0,0 +1,99 @@
+use crate::error::AocError;
+use crate::Solution;
+
+/// The earliest departure time, and the bus IDs in order, with out of service buses as `None`
+pub struct Notes
+{
+  earliest: usize,
+  buses: Vec<Option<usize>>,
+}
+
+// values = (m, t) where t is the order of departure, and m is the bus ID / modulo
+fn chinese_remainder_theorem(enumerated: Vec<(usize, usize)>) -> usize
+{
+  let mut values = enumerated.into_iter().map(|(m, t)| (m, (m - (t % m)) % m)).collect::<Vec<_>>();
+  values.sort_by_key(|(m1, _)| *m1);
+  let (mut inc, mut acc) = values.pop().unwrap();
+  for (m, t) in values.into_iter().rev() {
+    while (acc % m) != t {
+      acc += inc;
+    }
+    inc *= m
+  }
+  acc
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+  type Input = Notes;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    let mut lines = input.lines();
+    let line = lines.next().ok_or_else(|| AocError::invalid_input("Missing departure time"))?;
+    let earliest = line.trim().parse::<usize>().map_err(|e| AocError::parse(1, line, e))?;
+    let line = lines.next().ok_or_else(|| AocError::invalid_input("Missing bus list"))?;
+    let buses = line
+      .split(',')
+      .map(|s| match s {
+        "x" => Ok(None),
+        _ => match s.parse::<usize>() {
+          Ok(0) => Err(format!("Invalid bus ID {:?}: Bus IDs must be positive", s)),
+          Ok(n) => Ok(Some(n)),
+          Err(e) => Err(format!("Invalid bus ID {:?}: {}", s, e))
+        }
+      })
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|e| AocError::parse(2, line, e))?;
+    if buses.iter().all(Option::is_none) {
+      return Err(AocError::invalid_input("No buses are in service"));
+    }
+    Ok(Notes { earliest, buses })
+  }
+
+  fn part1(notes: &Notes) -> Result<usize, AocError>
+  {
+    let nums = notes.buses
+      .iter()
+      .flatten()
+      .map(|&n| (n, n - (notes.earliest % n)))
+      .min_by(|(_, n), (_, b)| n.cmp(b))
+      .ok_or(AocError::NoSolution)?;
+    Ok(nums.0 * nums.1)
+  }
+
+  fn part2(notes: &Notes) -> Result<usize, AocError>
+  {
+    let nums = notes.buses
+      .iter()
+      .enumerate()
+      .filter_map(|(i, m)| m.map(|m| (m, i)))
+      .collect::<Vec<_>>();
+    Ok(chinese_remainder_theorem(nums))
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn test_chinese_remainder_theorem()
+  {
+    let values: Vec<(usize, usize)> = vec![(7, 0), (13, 1), (59, 4), (31, 6), (19, 7)];
+    assert_eq!(chinese_remainder_theorem(values), 1068781);
+  }
+
+  #[test]
+  fn test_parse()
+  {
+    assert_eq!(Day13::parse("939\n7,13,x,x,59").unwrap().buses, vec![Some(7), Some(13), None, None, Some(59)]);
+    assert!(matches!(Day13::parse("5\n0,7"), Err(AocError::Parse { line: 2, .. })));
+    assert!(matches!(Day13::parse("5\n00,7"), Err(AocError::Parse { line: 2, .. })));
+    assert!(matches!(Day13::parse("5\nx,x"), Err(AocError::InvalidInput(_))));
+  }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use crate::error::AocError;
+use crate::utils::parse_lines;
+use crate::Solution;
+
+// Represents a mask of the form "0XX10XX0110"
+//
+// This is used to mask a value like so: (V & filter) + values
+//
+// The example above results in the following values:
+// - filter: 01100110000
+// - values: 00010000110
+struct Mask
+{
+  filter: usize,
+  values: usize,
+}
+
+impl Mask {
+  fn new() -> Self
+  {
+    Mask { filter: 0xFFFFFFFF, values: 0 }
+  }
+
+  fn from_string(s: &str) -> Self
+  {
+    let mut filter = 0usize;
+    let mut values = 0usize;
+    for c in s.chars()
+    {
+      let (f, v) = match c {
+        '0' => (0, 0),
+        '1' => (0, 1),
+        'X' => (1, 0),
+        _ => (1, 0)
+      };
+      filter = (filter << 1) + f;
+      values = (values << 1) + v
+    }
+    Mask { filter, values }
+  }
+
+  fn mask_value(&self, n: usize) -> usize
+  {
+    (n & self.filter) + self.values
+  }
+}
+
+struct MaskV2
+{
+  filter: u64,
+  junctions: Vec<u64>,
+}
+
+impl MaskV2
+{
+  fn from_string(s: &str) -> Self
+  {
+    let mut junctions = Vec::<u64>::new();
+    let mut filter = 0u64;
+    for (i, c) in s.chars().rev().enumerate() {
+      let bit = match (i, c) {
+        (_, '1') => 1,
+        (_, '0') => 0,
+        (i, 'X') => {
+          junctions.push(i as u64);
+          0
+        }
+        _ => unreachable!()
+      };
+      filter |= bit << i ;
+    }
+
+    MaskV2 { filter, junctions }
+  }
+
+  fn mask_address(&self, a: u64) -> Vec<u64>
+  {
+    let mut addresses = vec![self.filter | a];
+    for j in &self.junctions {
+      let mut new_addresses = vec![];
+      for a in addresses {
+        let mask = 1 << j;
+        new_addresses.push(a & (!mask));
+        new_addresses.push(a | mask);
+      }
+      addresses = new_addresses
+    }
+    addresses
+  }
+}
+
+fn parse_memory_assignment(s: &str) -> Result<(u64, usize), String>
+{
+  let left_bracket = s.find('[').ok_or("Missing '['")?;
+  let right_bracket = s.find(']').ok_or("Missing ']'")?;
+  let equals = s.find('=').ok_or("Missing '='")?;
+  let address = s.get((left_bracket + 1)..right_bracket).ok_or("Misplaced ']'")?;
+  Ok((
+    address.trim().parse::<u64>().map_err(|e| format!("Invalid address: {}", e))?,
+    s[(equals + 1)..].trim().parse::<usize>().map_err(|e| format!("Invalid value: {}", e))?
+  ))
+}
+
+fn parse_mask(s: &str) -> Result<String, String>
+{
+  let mask = s.trim();
+  if mask.len() > 36 {
+    Err(format!("Mask is {} bits long, expected at most 36", mask.len()))
+  } else if let Some(c) = mask.chars().find(|c| !"01X".contains(*c)) {
+    Err(format!("Invalid mask bit {:?}", c))
+  } else {
+    Ok(mask.to_string())
+  }
+}
+
+/// A line of the initialization program
+pub enum Command
+{
+  SetMask(String),
+  Assign(u64, usize),
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+  type Input = Vec<Command>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    parse_lines(input, |l| {
+      if l.starts_with("mem") {
+        let (a, v) = parse_memory_assignment(l)?;
+        Ok(Command::Assign(a, v))
+      } else if let Some(mask) = l.strip_prefix("mask =") {
+        Ok(Command::SetMask(parse_mask(mask)?))
+      } else {
+        Err("Expected a mask or a memory assignment".to_string())
+      }
+    })
+  }
+
+  fn part1(commands: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut memory = HashMap::<u64, usize>::new();
+    let mut mask = Mask::new();
+    for c in commands {
+      match c {
+        Command::Assign(a, v) => { memory.insert(*a, mask.mask_value(*v)); }
+        Command::SetMask(m) => mask = Mask::from_string(m)
+      }
+    }
+
+    Ok(memory.values().sum())
+  }
+
+  fn part2(commands: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut memory = HashMap::<u64, usize>::new();
+    let mut mask = MaskV2 { filter: 0, junctions: vec![] };
+    for c in commands {
+      match c {
+        Command::Assign(a, v) => {
+          let addresses = mask.mask_address(*a);
+          for a in addresses {
+            memory.insert(a, *v);
+          }
+        }
+        Command::SetMask(m) => mask = MaskV2::from_string(m)
+      }
+    }
+
+    Ok(memory.values().sum())
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn test_read_mask()
+  {
+    let mask = Mask::from_string("0XX10XX0110");
+    assert_eq!(mask.filter, 0b01100110000);
+    assert_eq!(mask.values, 0b00010000110);
+  }
+
+  #[test]
+  fn test_mask_values()
+  {
+    let mask = Mask::from_string("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
+
+    assert_eq!(mask.mask_value(11), 73);
+    assert_eq!(mask.mask_value(101), 101);
+    assert_eq!(mask.mask_value(0), 64);
+  }
+
+  #[test]
+  fn test_parse_mem_assignment()
+  {
+    assert_eq!(parse_memory_assignment("mem[10] = 47892"), Ok((10, 47892)));
+    assert_eq!(parse_memory_assignment("mem[2450] = 68156"), Ok((2450, 68156)));
+    assert_eq!(parse_memory_assignment("mem[123230] = 4741892"), Ok((123230, 4741892)));
+    assert!(parse_memory_assignment("mem[12 = 4").is_err());
+    assert!(parse_memory_assignment("mem[a] = 4").is_err());
+  }
+
+  #[test]
+  fn test_mask_address()
+  {
+    let mask = MaskV2::from_string("000000000000000000000000000000X1001X");
+    assert_eq!(mask.filter, 0b10010);
+    assert_eq!(mask.junctions, vec![0, 5]);
+
+    let mut addresses = mask.mask_address(42);
+    addresses.sort();
+
+    assert_eq!(addresses, vec![26, 27, 58, 59]);
+  }
+}
\ No newline at end of file
@@ -1,4 +1,7 @@
 use std::collections::HashMap;
+use crate::error::AocError;
+use crate::Solution;
+use super::day15_vec::parse_starting_numbers;
 
 struct NumberList
 {
@@ -9,7 +12,7 @@ struct NumberList
 
 impl NumberList
 {
-  fn new(starting_numbers: &Vec<usize>) -> Self
+  fn new(starting_numbers: &[usize]) -> Self
   {
     let last_num = *starting_numbers.last().unwrap();
     let last_use = starting_numbers.iter().take(starting_numbers.len() - 1).enumerate().map(|(i, n)| (*n, i)).collect();
@@ -19,7 +22,7 @@ impl NumberList
 
   fn get_last_used(&self, n: usize) -> Option<usize>
   {
-    self.last_use.get(&n).map(|n| *n)
+    self.last_use.get(&n).copied()
   }
 
   fn set_last_used(&mut self, n: usize, turn: usize)
@@ -51,21 +54,29 @@ impl NumberList
   }
 }
 
-fn problem1() -> usize
-{
-  let mut list = NumberList::new(&vec![9, 6, 0, 10, 18, 2, 1]);
-  list.run_until_turn(2020)
-}
+pub struct Day15;
 
-fn problem2() -> usize
-{
-  let mut list = NumberList::new(&vec![9, 6, 0, 10, 18, 2, 1]);
-  list.run_until_turn(30000000)
-}
+impl Solution for Day15 {
+  type Input = Vec<usize>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    parse_starting_numbers(input)
+  }
+
+  fn part1(starting_numbers: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut list = NumberList::new(starting_numbers);
+    Ok(list.run_until_turn(2020))
+  }
 
-fn main() {
-  println!("Result of problem 1: {}", problem1());
-  println!("Result of problem 2: {}", problem2());
+  fn part2(starting_numbers: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut list = NumberList::new(starting_numbers);
+    Ok(list.run_until_turn(30000000))
+  }
 }
 
 #[cfg(test)]
@@ -76,7 +87,7 @@ mod tests
   #[test]
   fn test_do_turn()
   {
-    let mut list = NumberList::new(&vec![0, 3, 6]);
+    let mut list = NumberList::new(&[0, 3, 6]);
 
     assert_eq!(list.do_turn(), 0);
     assert_eq!(list.do_turn(), 3);
@@ -90,11 +101,11 @@ mod tests
   #[test]
   fn test_run_until_turn_2020()
   {
-    let mut list1 = NumberList::new(&vec![0, 3, 6]);
-    let mut list2 = NumberList::new(&vec![1, 3, 2]);
-    let mut list3 = NumberList::new(&vec![2, 1, 3]);
-    let mut list4 = NumberList::new(&vec![1, 2, 3]);
-    let mut list5 = NumberList::new(&vec![2, 3, 1]);
+    let mut list1 = NumberList::new(&[0, 3, 6]);
+    let mut list2 = NumberList::new(&[1, 3, 2]);
+    let mut list3 = NumberList::new(&[2, 1, 3]);
+    let mut list4 = NumberList::new(&[1, 2, 3]);
+    let mut list5 = NumberList::new(&[2, 3, 1]);
 
     assert_eq!(list1.run_until_turn(2020), 436);
     assert_eq!(list2.run_until_turn(2020), 1);
@@ -106,11 +117,11 @@ mod tests
   #[test]
   fn test_run_until_turn_30000000()
   {
-    let mut list1 = NumberList::new(&vec![0, 3, 6]);
-    let mut list2 = NumberList::new(&vec![1, 3, 2]);
-    let mut list3 = NumberList::new(&vec![2, 1, 3]);
-    let mut list4 = NumberList::new(&vec![1, 2, 3]);
-    let mut list5 = NumberList::new(&vec![2, 3, 1]);
+    let mut list1 = NumberList::new(&[0, 3, 6]);
+    let mut list2 = NumberList::new(&[1, 3, 2]);
+    let mut list3 = NumberList::new(&[2, 1, 3]);
+    let mut list4 = NumberList::new(&[1, 2, 3]);
+    let mut list5 = NumberList::new(&[2, 3, 1]);
 
     assert_eq!(list1.run_until_turn(30000000), 175594);
     assert_eq!(list2.run_until_turn(30000000), 2578);
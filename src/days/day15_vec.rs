@@ -1,3 +1,7 @@
+use crate::error::AocError;
+use std::cmp::max;
+use crate::Solution;
+
 struct NumberList
 {
   last_num: usize,
@@ -7,12 +11,13 @@ struct NumberList
 
 impl NumberList
 {
-  fn new(starting_numbers: &Vec<usize>) -> Self
+  fn new(starting_numbers: &[usize]) -> Self
   {
     let last_num = *starting_numbers.last().unwrap();
     let turn = starting_numbers.len();
     let mut list = NumberList { last_num, last_use: Vec::<Option<usize>>::new(), turn };
-    list.last_use.resize(256, None);
+    let highest = starting_numbers.iter().max().copied().unwrap_or(0);
+    list.last_use.resize(max(256, highest + 1), None);
     for (i, &n) in starting_numbers.iter().take(starting_numbers.len() - 1).enumerate() {
       list.set_last_used(n, i)
     }
@@ -45,7 +50,9 @@ impl NumberList
 
   fn run_until_turn(&mut self, turn: usize) -> usize
   {
-    self.last_use.resize(turn + 1, None);
+    if self.last_use.len() <= turn {
+      self.last_use.resize(turn + 1, None);
+    }
     while self.turn < (turn - 1) {
       self.do_turn();
     }
@@ -53,21 +60,42 @@ impl NumberList
   }
 }
 
-fn problem1() -> usize
+/// Parse the comma separated starting numbers, shared by both implementations
+pub(crate) fn parse_starting_numbers(input: &str) -> Result<Vec<usize>, AocError>
 {
-  let mut list = NumberList::new(&vec![9, 6, 0, 10, 18, 2, 1]);
-  list.run_until_turn(2020)
+  let line = input.trim();
+  if line.is_empty() {
+    return Err(AocError::invalid_input("No starting numbers"));
+  }
+  line.split(',')
+    .map(|n| n.trim().parse::<usize>())
+    .collect::<Result<_, _>>()
+    .map_err(|e| AocError::parse(1, line, e))
 }
 
-fn problem2() -> usize
-{
-  let mut list = NumberList::new(&vec![9, 6, 0, 10, 18, 2, 1]);
-  list.run_until_turn(30000000)
-}
+pub struct Day15;
+
+impl Solution for Day15 {
+  type Input = Vec<usize>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    parse_starting_numbers(input)
+  }
 
-fn main() {
-  println!("Result of problem 1: {}", problem1());
-  println!("Result of problem 2: {}", problem2());
+  fn part1(starting_numbers: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut list = NumberList::new(starting_numbers);
+    Ok(list.run_until_turn(2020))
+  }
+
+  fn part2(starting_numbers: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut list = NumberList::new(starting_numbers);
+    Ok(list.run_until_turn(30000000))
+  }
 }
 
 #[cfg(test)]
@@ -78,7 +106,7 @@ mod tests
   #[test]
   fn test_do_turn()
   {
-    let mut list = NumberList::new(&vec![0, 3, 6]);
+    let mut list = NumberList::new(&[0, 3, 6]);
 
     assert_eq!(list.do_turn(), 0);
     assert_eq!(list.do_turn(), 3);
@@ -92,11 +120,11 @@ mod tests
   #[test]
   fn test_run_until_turn_2020()
   {
-    let mut list1 = NumberList::new(&vec![0, 3, 6]);
-    let mut list2 = NumberList::new(&vec![1, 3, 2]);
-    let mut list3 = NumberList::new(&vec![2, 1, 3]);
-    let mut list4 = NumberList::new(&vec![1, 2, 3]);
-    let mut list5 = NumberList::new(&vec![2, 3, 1]);
+    let mut list1 = NumberList::new(&[0, 3, 6]);
+    let mut list2 = NumberList::new(&[1, 3, 2]);
+    let mut list3 = NumberList::new(&[2, 1, 3]);
+    let mut list4 = NumberList::new(&[1, 2, 3]);
+    let mut list5 = NumberList::new(&[2, 3, 1]);
 
     assert_eq!(list1.run_until_turn(2020), 436);
     assert_eq!(list2.run_until_turn(2020), 1);
@@ -108,11 +136,11 @@ mod tests
   #[test]
   fn test_run_until_turn_30000000()
   {
-    let mut list1 = NumberList::new(&vec![0, 3, 6]);
-    let mut list2 = NumberList::new(&vec![1, 3, 2]);
-    let mut list3 = NumberList::new(&vec![2, 1, 3]);
-    let mut list4 = NumberList::new(&vec![1, 2, 3]);
-    let mut list5 = NumberList::new(&vec![2, 3, 1]);
+    let mut list1 = NumberList::new(&[0, 3, 6]);
+    let mut list2 = NumberList::new(&[1, 3, 2]);
+    let mut list3 = NumberList::new(&[2, 1, 3]);
+    let mut list4 = NumberList::new(&[1, 2, 3]);
+    let mut list5 = NumberList::new(&[2, 3, 1]);
 
     assert_eq!(list1.run_until_turn(30000000), 175594);
     assert_eq!(list2.run_until_turn(30000000), 2578);
@@ -0,0 +1,246 @@
+use std::ops::RangeInclusive;
+use std::collections::{HashMap, HashSet};
+use crate::error::AocError;
+use crate::utils::split_in_two;
+use crate::Solution;
+
+pub struct Ticket(Vec<usize>);
+
+impl Ticket
+{
+  fn from_string(string: &str) -> Result<Ticket, String>
+  {
+    string.split(',')
+      .map(|s| s.parse::<usize>().map_err(|e| format!("Invalid ticket value {:?}: {}", s, e)))
+      .collect::<Result<_, _>>()
+      .map(Ticket)
+  }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Classifier
+{
+  left: RangeInclusive<usize>,
+  right: RangeInclusive<usize>,
+}
+
+impl Classifier
+{
+  fn contains(&self, n: usize) -> bool
+  {
+    self.left.contains(&n) || self.right.contains(&n)
+  }
+}
+
+impl Classifier
+{
+  fn from_string(string: &str) -> Result<Classifier, String>
+  {
+    let range_from_str = |s: &str| {
+      let [low, high] = split_in_two(s, "-").ok_or(format!("Invalid range {:?}", s))?;
+      let parse = |n: &str| n.parse::<usize>().map_err(|e| format!("Invalid range {:?}: {}", s, e));
+      Ok::<_, String>(RangeInclusive::new(parse(&low)?, parse(&high)?))
+    };
+
+    let [left, right] = split_in_two(string, " or ").ok_or("Expected two ranges separated by \" or \"")?;
+    Ok(Classifier { left: range_from_str(&left)?, right: range_from_str(&right)? })
+  }
+}
+
+pub struct Classifiers(HashMap<String, Classifier>);
+
+impl Classifiers
+{
+  fn from_lines<T, I>(lines: I) -> Result<Classifiers, AocError>
+    where I: Iterator<Item=T>,
+          T: AsRef<str>
+  {
+    lines
+      .enumerate()
+      .map(|(i, line)| {
+        let line = line.as_ref();
+        split_in_two(line, ": ")
+          .ok_or_else(|| "Expected a field name followed by \": \"".to_string())
+          .and_then(|[name, ranges]| Ok((name, Classifier::from_string(&ranges)?)))
+          .map_err(|e| AocError::parse(i + 1, line, e))
+      })
+      .collect::<Result<_, _>>()
+      .map(Classifiers)
+  }
+
+  fn validate_ticket(&self, ticket: &Ticket) -> Result<usize, usize>
+  {
+    match ticket.0.iter().filter_map(|n|
+      if self.0.iter().any(|(_, c)| c.contains(*n)) {
+        None
+      } else {
+        Some(*n)
+      }
+    ).next() {
+      None => Ok(0),
+      Some(n) => Err(n)
+    }
+  }
+}
+
+fn parse<I, T>(lines: I) -> Result<(Classifiers, Vec<Ticket>), AocError>
+  where I: Iterator<Item=T>,
+        T: AsRef<str>
+{
+  let mut lines = lines.enumerate().map(|(i, l)| (i + 1, l));
+  let classifiers = lines.by_ref().take_while(|(_, s)| !s.as_ref().is_empty()).map(|(_, s)| s).collect::<Vec<_>>();
+  let classifiers = Classifiers::from_lines(classifiers.into_iter())?;
+  let your_ticket = lines.by_ref().nth(1).ok_or_else(|| AocError::invalid_input("Missing your ticket"))?;
+  let mut other_tickets = lines.skip(2).collect::<Vec<_>>();
+  other_tickets.insert(0, your_ticket);
+
+  let num_fields = classifiers.0.len();
+  let tickets = other_tickets.iter()
+    .map(|(i, t)| {
+      let t = t.as_ref();
+      match Ticket::from_string(t) {
+        Ok(ticket) if ticket.0.len() != num_fields => Err(format!("Expected {} fields, found {}", num_fields, ticket.0.len())),
+        result => result
+      }.map_err(|e| AocError::parse(*i, t, e))
+    })
+    .collect::<Result<_, _>>()?;
+  Ok((classifiers, tickets))
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+  type Input = (Classifiers, Vec<Ticket>);
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    parse(input.lines())
+  }
+
+  fn part1((classifiers, tickets): &Self::Input) -> Result<usize, AocError>
+  {
+    Ok(tickets.iter().skip(1).filter_map(|t| classifiers.validate_ticket(t).err()).sum())
+  }
+
+  fn part2((classifiers, tickets): &Self::Input) -> Result<usize, AocError>
+  {
+    let my_ticket = tickets.first().ok_or(AocError::NoSolution)?;
+    let valid_tickets = tickets.iter().skip(1).filter(|t| classifiers.validate_ticket(t).is_ok()).collect::<Vec<_>>();
+    let possible_classifiers = classifiers.0.keys().collect::<HashSet<_>>();
+    let mut key_order = Vec::<Vec<String>>::new();
+    let num_keys = my_ticket.0.len();
+
+    for i in 0..num_keys {
+      let mut classifier_keys = possible_classifiers.iter().map(|&s| s.clone()).collect::<Vec<_>>();
+      for t in &valid_tickets
+      {
+        let ticket_entry = t.0[i];
+        classifier_keys.retain(|c| classifiers.0[c].contains(ticket_entry));
+      }
+
+      key_order.push(classifier_keys);
+    }
+
+    let mut found: HashMap<String, usize> = HashMap::with_capacity(num_keys);
+    for _ in 0..num_keys {
+      let (unique_index, unique_key) = key_order.iter()
+        .enumerate()
+        .find_map(|(i, v)|
+          if v.len() == 1 {
+            Some((i, v[0].clone()))
+          } else {
+            None
+          })
+        .ok_or(AocError::NoSolution)?;
+
+      // Filter out found keys
+      key_order = key_order.iter()
+        .map(|o|
+          o.iter()
+            .filter(|&s| s != &unique_key)
+            .cloned()
+            .collect())
+        .collect();
+
+      found.insert(unique_key, unique_index);
+    }
+
+    Ok(found.into_iter().filter_map(|(k,i)|
+      if k.starts_with("departure") {
+        Some(my_ticket.0[i])
+      } else {
+        None
+      }
+    ).product())
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn test_parse_ticket()
+  {
+    assert_eq!(Ticket::from_string("7,1,14").unwrap().0, vec![7, 1, 14]);
+    assert_eq!(Ticket::from_string("7,3,47").unwrap().0, vec![7, 3, 47]);
+    assert!(Ticket::from_string("7,,47").is_err());
+  }
+
+  #[test]
+  fn test_validate_ticket()
+  {
+    let classifiers = Classifiers::from_lines(r"class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50".lines()).unwrap();
+
+    assert_eq!(classifiers.validate_ticket(&Ticket::from_string("7,1,14").unwrap()), Ok(0));
+    assert_eq!(classifiers.validate_ticket(&Ticket::from_string("7,3,47").unwrap()), Ok(0));
+    assert_eq!(classifiers.validate_ticket(&Ticket::from_string("40,4,50").unwrap()), Err(4));
+    assert_eq!(classifiers.validate_ticket(&Ticket::from_string("55,2,20").unwrap()), Err(55));
+    assert_eq!(classifiers.validate_ticket(&Ticket::from_string("38,6,12").unwrap()), Err(12));
+  }
+
+  #[test]
+  fn test_parse_classifiers() {
+    let classifiers = Classifiers::from_lines(r"class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50".lines()).unwrap();
+
+    assert_eq!(Classifier { left: RangeInclusive::new(1, 3), right: RangeInclusive::new(5, 7) }, classifiers.0["class"]);
+    assert_eq!(Classifier { left: RangeInclusive::new(6, 11), right: RangeInclusive::new(33, 44) }, classifiers.0["row"]);
+    assert_eq!(Classifier { left: RangeInclusive::new(13, 40), right: RangeInclusive::new(45, 50) }, classifiers.0["seat"]);
+  }
+
+  #[test]
+  fn test_parse()
+  {
+    let it = r"class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12".lines();
+
+    let (classifiers, tickets) = parse(it).unwrap();
+
+    assert_eq!(Classifier { left: RangeInclusive::new(1, 3), right: RangeInclusive::new(5, 7) }, classifiers.0["class"]);
+    assert_eq!(Classifier { left: RangeInclusive::new(6, 11), right: RangeInclusive::new(33, 44) }, classifiers.0["row"]);
+    assert_eq!(Classifier { left: RangeInclusive::new(13, 40), right: RangeInclusive::new(45, 50) }, classifiers.0["seat"]);
+
+    assert_eq!(tickets[0].0, vec![7, 1, 14]);
+    assert_eq!(tickets[1].0, vec![7, 3, 47]);
+    assert_eq!(tickets[2].0, vec![40, 4, 50]);
+    assert_eq!(tickets[3].0, vec![55, 2, 20]);
+    assert_eq!(tickets[4].0, vec![38, 6, 12]);
+  }
+}
\ No newline at end of file
@@ -1,14 +1,15 @@
-use std::path::Path;
 use std::ops::RangeInclusive;
 use std::collections::HashSet;
 use std::cmp::{min, max};
-use AoC2020::utils::read_lines;
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::Solution;
 use std::hash::Hash;
 
 trait Voxel
 {
   fn as_vec(&self) -> Vec<isize>;
-  fn from_vec(v: &Vec<isize>) -> Self;
+  fn from_vec(v: &[isize]) -> Self;
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
@@ -20,10 +21,6 @@ struct VoxelCoord3D
 }
 
 impl VoxelCoord3D {
-  fn new() -> Self {
-    VoxelCoord3D { x: 0, y: 0, z: 0 }
-  }
-
   fn with_coords(x: isize, y: isize, z: isize) -> Self
   {
     VoxelCoord3D { x, y, z }
@@ -36,7 +33,7 @@ impl Voxel for VoxelCoord3D {
     vec![self.x, self.y, self.z]
   }
 
-  fn from_vec(v: &Vec<isize>) -> Self
+  fn from_vec(v: &[isize]) -> Self
   {
     VoxelCoord3D { x: v[0], y: v[1], z: v[2] }
   }
@@ -52,10 +49,6 @@ struct VoxelCoord4D
 }
 
 impl VoxelCoord4D {
-  fn new() -> Self {
-    VoxelCoord4D { x: 0, y: 0, z: 0, w: 0 }
-  }
-
   fn with_coords(x: isize, y: isize, z: isize, w: isize) -> Self
   {
     VoxelCoord4D { x, y, z, w }
@@ -68,7 +61,7 @@ impl Voxel for VoxelCoord4D {
     vec![self.x, self.y, self.z, self.w]
   }
 
-  fn from_vec(v: &Vec<isize>) -> Self
+  fn from_vec(v: &[isize]) -> Self
   {
     VoxelCoord4D { x: v[0], y: v[1], z: v[2], w: v[3] }
   }
@@ -81,7 +74,7 @@ struct RangeVec
 
 impl RangeVec
 {
-  fn from_min_max(min: &Vec<isize>, max: &Vec<isize>) -> Self
+  fn from_min_max(min: &[isize], max: &[isize]) -> Self
   {
     RangeVec {
       ranges: min.iter()
@@ -91,11 +84,11 @@ impl RangeVec
     }
   }
 
-  fn delve(r: &Vec<RangeInclusive<isize>>) -> Vec<Vec<isize>> {
+  fn delve(r: &[RangeInclusive<isize>]) -> Vec<Vec<isize>> {
     if r.len() == 1 {
       r[0].clone().map(|i| vec![i]).collect()
     } else {
-      let next_vec = r[1..].iter().cloned().collect::<Vec<_>>();
+      let next_vec = r[1..].to_vec();
       let mut return_vec = Vec::<Vec<isize>>::new();
       for i in r[0].clone() {
         return_vec.extend(
@@ -131,7 +124,7 @@ impl<V> VoxelSpace<V>
   fn count_active_neighbours_vec(&self, v: Vec<isize>) -> usize
   {
     let mut count = 0;
-    let range_vec = RangeVec::from_min_max(&v.iter().map(|&i| i - 1).collect(), &v.iter().map(|&i| i + 1).collect());
+    let range_vec = RangeVec::from_min_max(&v.iter().map(|&i| i - 1).collect::<Vec<_>>(), &v.iter().map(|&i| i + 1).collect::<Vec<_>>());
     for c in range_vec.get_all_included_coords().into_iter()
     {
       let coord = V::from_vec(&c);
@@ -142,10 +135,11 @@ impl<V> VoxelSpace<V>
     count
   }
 
-  fn get_affected_bounds(&self) -> RangeVec
+  /// The bounds of the active cubes and their neighbours, or None if no cubes are active
+  fn get_affected_bounds(&self) -> Option<RangeVec>
   {
     let mut v_it = self.voxels.iter();
-    let first = v_it.by_ref().next().unwrap().as_vec();
+    let first = v_it.by_ref().next()?.as_vec();
     let (min, max) = v_it
       .map(|v| v.as_vec())
       .fold((first.clone(), first),
@@ -159,7 +153,7 @@ impl<V> VoxelSpace<V>
     //   .zip(max.iter())
     //   .map(|(l, r)| RangeInclusive::new(l - 1, r + 1))
     //   .collect()
-    RangeVec::from_min_max(&min.iter().map(|&i| i - 1).collect(), &max.iter().map(|&i| i + 1).collect())
+    Some(RangeVec::from_min_max(&min.iter().map(|&i| i - 1).collect::<Vec<_>>(), &max.iter().map(|&i| i + 1).collect::<Vec<_>>()))
   }
 }
 
@@ -174,6 +168,7 @@ impl<> VoxelSpace<VoxelCoord3D> {
     self.voxels.insert(VoxelCoord3D::with_coords(x, y, z));
   }
 
+  #[cfg(test)]
   fn set_inactive(&mut self, x: isize, y: isize, z: isize)
   {
     self.voxels.remove(&VoxelCoord3D::with_coords(x, y, z));
@@ -219,11 +214,6 @@ impl<> VoxelSpace<VoxelCoord4D> {
     self.voxels.insert(VoxelCoord4D::with_coords(x, y, z, w));
   }
 
-  fn set_inactive(&mut self, x: isize, y: isize, z: isize, w: isize)
-  {
-    self.voxels.remove(&VoxelCoord4D::with_coords(x, y, z, w));
-  }
-
   fn is_active(&self, x: isize, y: isize, z: isize, w: isize) -> bool
   {
     self.voxels.contains(&VoxelCoord4D::with_coords(x, y, z, w))
@@ -255,8 +245,11 @@ impl<> VoxelSpace<VoxelCoord4D> {
 
 fn do_step(space: &VoxelSpace<VoxelCoord3D>) -> VoxelSpace<VoxelCoord3D>
 {
-  let r = space.get_affected_bounds();
   let mut new_space = VoxelSpace::<VoxelCoord3D>::new();
+  let r = match space.get_affected_bounds() {
+    Some(r) => r,
+    None => return new_space
+  };
 
   for i in r.get_all_included_coords()
   {
@@ -273,8 +266,11 @@ fn do_step(space: &VoxelSpace<VoxelCoord3D>) -> VoxelSpace<VoxelCoord3D>
 
 fn do_step_4d(space: &VoxelSpace<VoxelCoord4D>) -> VoxelSpace<VoxelCoord4D>
 {
-  let r = space.get_affected_bounds();
   let mut new_space = VoxelSpace::<VoxelCoord4D>::new();
+  let r = match space.get_affected_bounds() {
+    Some(r) => r,
+    None => return new_space
+  };
 
   for i in r.get_all_included_coords()
   {
@@ -289,56 +285,69 @@ fn do_step_4d(space: &VoxelSpace<VoxelCoord4D>) -> VoxelSpace<VoxelCoord4D>
   new_space
 }
 
-fn file_to_space(path: &Path) -> VoxelSpace<VoxelCoord3D>
+fn active_cubes(slice: &Grid<bool>) -> impl Iterator<Item=(isize, isize)> + '_
+{
+  slice.indexed()
+    .filter(|(_, &active)| active)
+    .map(|((x, y), _)| (x as isize, y as isize))
+}
+
+fn slice_to_space(slice: &Grid<bool>) -> VoxelSpace<VoxelCoord3D>
 {
   let mut space = VoxelSpace::<VoxelCoord3D>::new();
-  for (y, l) in read_lines(&path).unwrap().enumerate().map(|(i, s)| (i, s.unwrap()))
+  for (x, y) in active_cubes(slice)
   {
-    for x in l.chars().enumerate().filter_map(|(i, c)| if c == '#' { Some(i) } else { None })
-    {
-      space.set_active(x as isize, y as isize, 0);
-    }
+    space.set_active(x, y, 0);
   }
   space
 }
 
-fn file_to_space_4d(path: &Path) -> VoxelSpace<VoxelCoord4D>
+fn slice_to_space_4d(slice: &Grid<bool>) -> VoxelSpace<VoxelCoord4D>
 {
   let mut space = VoxelSpace::<VoxelCoord4D>::new();
-  for (y, l) in read_lines(&path).unwrap().enumerate().map(|(i, s)| (i, s.unwrap()))
+  for (x, y) in active_cubes(slice)
   {
-    for x in l.chars().enumerate().filter_map(|(i, c)| if c == '#' { Some(i) } else { None })
-    {
-      space.set_active(x as isize, y as isize, 0, 0);
-    }
+    space.set_active(x, y, 0, 0);
   }
   space
 }
 
-fn problem1(path: &Path) -> usize
-{
-  let mut space = file_to_space(&path);
-  for _ in 0..6 {
-    space = do_step(&space);
+pub struct Day17;
+
+impl Solution for Day17 {
+  /// The initial slice, where active cubes are true
+  type Input = Grid<bool>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    Grid::parse(input, |c| match c {
+      '#' => Ok(true),
+      '.' => Ok(false),
+      _ => Err(format!("Invalid cube {:?}", c))
+    })
   }
 
-  space.voxels.len()
-}
+  fn part1(active: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut space = slice_to_space(active);
+    for _ in 0..6 {
+      space = do_step(&space);
+    }
 
-fn problem2(path: &Path) -> usize
-{
-  let mut space = file_to_space_4d(&path);
-  for _ in 0..6 {
-    space = do_step_4d(&space);
+    Ok(space.voxels.len())
   }
 
-  space.voxels.len()
-}
+  fn part2(active: &Self::Input) -> Result<usize, AocError>
+  {
+    let mut space = slice_to_space_4d(active);
+    for _ in 0..6 {
+      space = do_step_4d(&space);
+    }
 
-fn main() {
-  let path = Path::new(r"data/17-1.txt");
-  println!("Result of problem 1: {}", problem1(path));
-  println!("Result of problem 2: {}", problem2(path));
+    Ok(space.voxels.len())
+  }
 }
 
 #[cfg(test)]
@@ -348,7 +357,7 @@ mod tests
 
   #[test]
   fn test_range_vec() {
-    let r = RangeVec::from_min_max(&vec![0, 0, 0], &vec![2, 2, 2]);
+    let r = RangeVec::from_min_max(&[0, 0, 0], &[2, 2, 2]);
 
     assert_eq!(r.get_all_included_coords(), vec![
       vec![0, 0, 0], vec![0, 0, 1], vec![0, 0, 2],
@@ -419,10 +428,13 @@ mod tests
     space.set_active(0, -1, 1);
     space.set_active(0, -1, -1);
 
-    let r = space.get_affected_bounds();
+    let r = space.get_affected_bounds().unwrap();
     assert_eq!(r.ranges[0], RangeInclusive::new(-2, 2));
     assert_eq!(r.ranges[1], RangeInclusive::new(-2, 1));
     assert_eq!(r.ranges[2], RangeInclusive::new(-2, 2));
+
+    assert!(VoxelSpace::<VoxelCoord3D>::new().get_affected_bounds().is_none());
+    assert_eq!(do_step(&VoxelSpace::<VoxelCoord3D>::new()).voxels.len(), 0);
   }
 
   #[test]
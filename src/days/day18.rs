@@ -1,8 +1,7 @@
-use std::path::Path;
-use std::ops::RangeInclusive;
-use std::collections::{HashSet, HashMap};
-use std::cmp::{min, max};
-use AoC2020::utils::read_lines;
+use std::collections::HashMap;
+use crate::error::AocError;
+use crate::utils::parse_lines;
+use crate::Solution;
 use std::hash::Hash;
 
 use nom::{
@@ -15,8 +14,6 @@ use nom::{
   number::complete::double,
   sequence::tuple,
 };
-use std::fmt;
-use std::fmt::Formatter;
 use nom::error::ErrorKind;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -41,7 +38,7 @@ pub enum Token {
 // }
 
 fn parse_operator(s: &str) -> IResult<&str, Operator> {
-  let (s, c) = take(1 as usize)(s)?;
+  let (s, c) = take(1_usize)(s)?;
   assert_eq!(c.len(), 1);
   Ok((
     s,
@@ -63,8 +60,8 @@ fn skip_whitespace(s: &str) -> IResult<&str, ()> {
   Ok((many0(one_of(" \t\x0c\n"))(s)?.0, ()))
 }
 
-pub fn parse(s: &str) -> Vec<Token> {
-  let (_, result) = fold_many1(
+pub fn parse(s: &str) -> Result<Vec<Token>, String> {
+  let (rest, result) = fold_many1(
     map(
       tuple((
         skip_whitespace,
@@ -82,26 +79,32 @@ pub fn parse(s: &str) -> Vec<Token> {
       acc.push(token);
       acc
     },
-  )(s).unwrap_or_default();
-  result
+  )(s).map_err(|e| e.to_string())?;
+  match rest.trim() {
+    "" => Ok(result),
+    rest => Err(format!("Unexpected {:?}", rest))
+  }
 }
 
 // Implementation of the shunting yard algorithm that executes operators as they're popped off the operator stack
-fn execute_calculation(tokens: &Vec<Token>, precedence: &HashMap<Operator, u8>) -> f64
+//
+// Returns None if the calculation is malformed, e.g. if the parentheses don't match up
+fn execute_calculation(tokens: &[Token], precedence: &HashMap<Operator, u8>) -> Option<f64>
 {
   let mut operators: Vec<(Operator, u8)> = vec![];
 
   let mut stack: Vec<f64> = vec![];
   let execute_operator = |op, stack: &mut Vec<f64>| {
-    let num1 = stack.pop().unwrap();
-    let num2 = stack.pop().unwrap();
+    let num1 = stack.pop()?;
+    let num2 = stack.pop()?;
     stack.push(match op {
       Operator::Minus => num1 - num2,
       Operator::Plus => num1 + num2,
       Operator::Multiply => num1 * num2,
       Operator::Divide => num1 / num2,
-      _ => panic!["Unsupported operator"]
-    })
+      _ => return None
+    });
+    Some(())
   };
 
   for token in tokens {
@@ -109,15 +112,19 @@ fn execute_calculation(tokens: &Vec<Token>, precedence: &HashMap<Operator, u8>)
       Token::Number(num) => stack.push(*num),
       Token::Operator(Operator::OpenParenthesis) => operators.push((Operator::OpenParenthesis, 10)),
       Token::Operator(Operator::CloseParenthesis) => {
-        while operators.last().unwrap().0 != Operator::OpenParenthesis {
-          execute_operator(operators.pop().unwrap().0, &mut stack);
+        while operators.last()?.0 != Operator::OpenParenthesis {
+          execute_operator(operators.pop()?.0, &mut stack)?;
         }
         operators.pop();
       }
       Token::Operator(op) => {
-        let p = *precedence.get(op).unwrap();
-        while !operators.is_empty() && operators.last().unwrap().1 >= p && operators.last().unwrap().0 != Operator::OpenParenthesis {
-          execute_operator(operators.pop().unwrap().0, &mut stack);
+        let p = *precedence.get(op)?;
+        while let Some(&(top, top_p)) = operators.last() {
+          if top_p < p || top == Operator::OpenParenthesis {
+            break;
+          }
+          execute_operator(top, &mut stack)?;
+          operators.pop();
         }
         operators.push((*op, p));
       }
@@ -125,38 +132,48 @@ fn execute_calculation(tokens: &Vec<Token>, precedence: &HashMap<Operator, u8>)
   }
 
   for (op, _) in operators.into_iter().rev() {
-    execute_operator(op, &mut stack);
+    execute_operator(op, &mut stack)?;
   }
 
-  assert_eq!(stack.len(), 1);
-
-  stack.pop().unwrap()
+  match stack[..] {
+    [result] => Some(result),
+    _ => None
+  }
 }
 
-fn problem1(path: &Path) -> usize
+fn sum_calculations(calculations: &[Vec<Token>], precedence: &HashMap<Operator, u8>) -> Result<usize, AocError>
 {
-  let precedence = [(Operator::Plus, 1u8), (Operator::Minus, 1u8), (Operator::Multiply, 1u8), (Operator::Divide, 1u8), (Operator::OpenParenthesis, 10u8), (Operator::CloseParenthesis, 10u8)].iter().cloned().collect();
-  read_lines(path)
-    .unwrap()
-    .map(|l| execute_calculation(&parse(&l.unwrap()), &precedence))
-    .fold(0.0, |l, r| l + r)
-    as usize
+  calculations.iter()
+    .enumerate()
+    .map(|(i, c)| execute_calculation(c, precedence)
+      .ok_or_else(|| AocError::invalid_input(format!("Calculation on line {} is malformed", i + 1))))
+    .sum::<Result<f64, _>>()
+    .map(|sum| sum as usize)
 }
 
-fn problem2(path: &Path) -> usize
-{
-  let precedence = [(Operator::Plus, 2u8), (Operator::Minus, 2u8), (Operator::Multiply, 1u8), (Operator::Divide, 1u8), (Operator::OpenParenthesis, 10u8), (Operator::CloseParenthesis, 10u8)].iter().cloned().collect();
-  read_lines(path)
-    .unwrap()
-    .map(|l| execute_calculation(&parse(&l.unwrap()), &precedence))
-    .fold(0.0, |l, r| l + r)
-    as usize
-}
+pub struct Day18;
+
+impl Solution for Day18 {
+  type Input = Vec<Vec<Token>>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    parse_lines(input, parse)
+  }
 
-fn main() {
-  let path = Path::new(r"data/18-1.txt");
-  println!("Result of problem 1: {}", problem1(path));
-  println!("Result of problem 2: {}", problem2(path));
+  fn part1(calculations: &Self::Input) -> Result<usize, AocError>
+  {
+    let precedence = [(Operator::Plus, 1u8), (Operator::Minus, 1u8), (Operator::Multiply, 1u8), (Operator::Divide, 1u8), (Operator::OpenParenthesis, 10u8), (Operator::CloseParenthesis, 10u8)].iter().cloned().collect();
+    sum_calculations(calculations, &precedence)
+  }
+
+  fn part2(calculations: &Self::Input) -> Result<usize, AocError>
+  {
+    let precedence = [(Operator::Plus, 2u8), (Operator::Minus, 2u8), (Operator::Multiply, 1u8), (Operator::Divide, 1u8), (Operator::OpenParenthesis, 10u8), (Operator::CloseParenthesis, 10u8)].iter().cloned().collect();
+    sum_calculations(calculations, &precedence)
+  }
 }
 
 #[cfg(test)]
@@ -167,10 +184,11 @@ mod tests
   #[test]
   fn test_parse() {
     assert_eq!(parse("1 + 2 * 3 + (4/5)"),
-               vec![Token::Number(1.0), Token::Operator(Operator::Plus), Token::Number(2.0), Token::Operator(Operator::Multiply),
+               Ok(vec![Token::Number(1.0), Token::Operator(Operator::Plus), Token::Number(2.0), Token::Operator(Operator::Multiply),
                     Token::Number(3.0), Token::Operator(Operator::Plus), Token::Operator(Operator::OpenParenthesis), Token::Number(4.0),
-                    Token::Operator(Operator::Divide), Token::Number(5.0), Token::Operator(Operator::CloseParenthesis)]
-    )
+                    Token::Operator(Operator::Divide), Token::Number(5.0), Token::Operator(Operator::CloseParenthesis)])
+    );
+    assert!(parse("1 + 2 x 3").is_err());
   }
 
   #[test]
@@ -179,14 +197,14 @@ mod tests
     let precedence = [(Operator::Plus, 1u8), (Operator::Minus, 1u8), (Operator::Multiply, 1u8), (Operator::Divide, 1u8), (Operator::OpenParenthesis, 10u8), (Operator::CloseParenthesis, 10u8)].iter().cloned().collect();
     // 1 + 2 * 3 + ((4 * 5)+1) == 30
     let result = execute_calculation(
-      &vec![Token::Number(1.0), Token::Operator(Operator::Plus), Token::Number(2.0), Token::Operator(Operator::Multiply),
+      &[Token::Number(1.0), Token::Operator(Operator::Plus), Token::Number(2.0), Token::Operator(Operator::Multiply),
             Token::Number(3.0), Token::Operator(Operator::Plus), Token::Operator(Operator::OpenParenthesis),
             Token::Operator(Operator::OpenParenthesis), Token::Number(4.0), Token::Operator(Operator::Multiply),
             Token::Number(5.0), Token::Operator(Operator::CloseParenthesis), Token::Operator(Operator::Plus),
             Token::Number(1.0), Token::Operator(Operator::CloseParenthesis)],
       &precedence,
     );
-    assert_eq!(result, 30.0);
+    assert_eq!(result, Some(30.0));
   }
 
   #[test]
@@ -195,11 +213,11 @@ mod tests
     // 2 * 3 + (4 * 5) == 46
     let precedence = [(Operator::Plus, 2u8), (Operator::Minus, 2u8), (Operator::Multiply, 1u8), (Operator::Divide, 1u8), (Operator::OpenParenthesis, 10u8), (Operator::CloseParenthesis, 10u8)].iter().cloned().collect();
     let result = execute_calculation(
-      &vec![Token::Number(2.0), Token::Operator(Operator::Multiply), Token::Number(3.0), Token::Operator(Operator::Plus),
+      &[Token::Number(2.0), Token::Operator(Operator::Multiply), Token::Number(3.0), Token::Operator(Operator::Plus),
             Token::Operator(Operator::OpenParenthesis), Token::Number(4.0), Token::Operator(Operator::Multiply),
             Token::Number(5.0), Token::Operator(Operator::CloseParenthesis)],
       &precedence,
     );
-    assert_eq!(result, 46.0);
+    assert_eq!(result, Some(46.0));
   }
 }
\ No newline at end of file
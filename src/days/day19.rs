@@ -1,12 +1,11 @@
-use std::path::Path;
-use std::ops::RangeInclusive;
-use std::collections::{HashSet, HashMap};
-use std::cmp::{min, max};
-use AoC2020::utils::{read_lines, split_in_two};
+use std::collections::HashMap;
+use crate::error::AocError;
+use crate::utils::split_in_two;
+use crate::Solution;
 use std::hash::Hash;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-enum Rule
+pub enum Rule
 {
   Sequence(Vec<Rule>),
   Alternative(Vec<Rule>),
@@ -14,51 +13,53 @@ enum Rule
   Character(char),
 }
 
-fn parse_rule(line: &str) -> (usize, Rule)
+fn parse_rule(line: &str) -> Result<(usize, Rule), String>
 {
-  let [id, sequence] = split_in_two(line, ": ");
+  let [id, sequence] = split_in_two(line, ": ").ok_or("Expected a rule ID followed by \": \"")?;
 
-  let id = id.parse::<usize>().unwrap();
+  let id = id.parse::<usize>().map_err(|e| format!("Invalid rule ID {:?}: {}", id, e))?;
   let sequence = sequence
     .split(" | ")
     .map(|l| {
-      let mut seq = l.split(" ")
+      let mut seq = l.split(' ')
         .map(|s| {
           let mut c = s.chars();
-          match c.next().unwrap()
+          match c.next()
           {
-            '"' => Rule::Character(c.next().unwrap()),
-            '0'..='9' => Rule::Reference(s.parse::<usize>().unwrap()),
-            _ => panic!("Invalid element {}", s)
+            Some('"') => c.next().map(Rule::Character).ok_or(format!("Invalid character {}", s)),
+            Some('0'..='9') => s.parse::<usize>().map(Rule::Reference).map_err(|e| format!("Invalid reference {}: {}", s, e)),
+            _ => Err(format!("Invalid element {:?}", s))
           }
-        }).collect::<Vec<Rule>>();
+        }).collect::<Result<Vec<Rule>, String>>()?;
 
-      if seq.len() == 1
+      Ok(if seq.len() == 1
       {
         seq.pop().unwrap()
       } else {
         Rule::Sequence(seq)
-      }
-    }).collect::<Vec<Rule>>();
+      })
+    }).collect::<Result<Vec<Rule>, String>>()?;
 
-  (id,
+  Ok((id,
    if sequence.len() == 1 {
      sequence[0].clone()
    } else {
      Rule::Alternative(sequence)
-   })
+   }))
 }
 
+#[cfg(test)]
 fn expand_rule(rule: &Rule, rules: &HashMap<usize, Rule>) -> Rule
 {
   match rule {
-    Rule::Alternative(a) => Rule::Alternative(a.iter().map(|s| expand_rule(s, &rules)).collect()),
-    Rule::Sequence(v) => Rule::Sequence(v.iter().map(|m| expand_rule(m, &rules)).collect()),
-    Rule::Reference(id) => expand_rule(rules.get(id).unwrap(), &rules),
+    Rule::Alternative(a) => Rule::Alternative(a.iter().map(|s| expand_rule(s, rules)).collect()),
+    Rule::Sequence(v) => Rule::Sequence(v.iter().map(|m| expand_rule(m, rules)).collect()),
+    Rule::Reference(id) => expand_rule(rules.get(id).unwrap(), rules),
     Rule::Character(_) => rule.clone()
   }
 }
 
+#[cfg(test)]
 fn expand_rules(rules: &HashMap<usize, Rule>) -> HashMap<usize, Rule>
 {
   rules.iter().map(|(id, rule)| (*id, expand_rule(rule, rules))).collect()
@@ -71,8 +72,7 @@ fn match_child(rule: &Rule, s: &str, rules: &HashMap<usize, Rule>) -> Option<usi
       a.iter().find_map(|r| match_child(r, s, rules))
     }
     Rule::Sequence(v) => {
-      v.iter().fold(Some(0usize), |acc, r| {
-        let count = acc?;
+      v.iter().try_fold(0usize, |count, r| {
         if count >= s.len() {
           None
         } else {
@@ -138,40 +138,48 @@ fn match_two_rules(rule1: &Rule, rule2: &Rule, s: &str, rules: &HashMap<usize, R
 
 }
 
-fn problem1(path: &Path) -> usize
+fn get_rule(rules: &HashMap<usize, Rule>, id: usize) -> Result<&Rule, AocError>
 {
-  let mut it = read_lines(path).unwrap();
-  let rules = it.by_ref().take_while(|s| !s.as_ref().unwrap().is_empty())
-    .map(|s| parse_rule(&s.unwrap()))
-    .collect::<HashMap<usize, Rule>>();
-
-  let rule0 = rules.get(&0).unwrap();
-  it.map(|s| if match_rule(&rule0, &s.unwrap(), &rules) { 1 } else { 0 }).sum()
+  rules.get(&id).ok_or_else(|| AocError::invalid_input(format!("Missing rule {}", id)))
 }
 
-fn problem2(path: &Path) -> usize
-{
-  let mut it = read_lines(path).unwrap();
-  let mut rules = it.by_ref().take_while(|s| !s.as_ref().unwrap().is_empty())
-    .map(|s| parse_rule(&s.unwrap()))
-    .collect::<HashMap<usize, Rule>>();
-
-  rules.insert(8, Rule::Alternative(vec![Rule::Sequence(vec![Rule::Reference(42), Rule::Reference(8)]), Rule::Reference(42)]));
-  rules.insert(11, Rule::Alternative(
-    vec![Rule::Sequence(vec![Rule::Reference(42), Rule::Reference(11), Rule::Reference(31)]),
-         Rule::Sequence(vec![Rule::Reference(42), Rule::Reference(31)])]));
-
-  let rule42 = rules.get(&42).unwrap();
-  let rule31 = rules.get(&31).unwrap();
-  it.map(|s| if match_two_rules(&rule42, &rule31, &s.unwrap(), &rules) { 1 } else { 0 }).sum()
-  // read_lines(path)
-  //   .unwrap()
-}
+pub struct Day19;
+
+impl Solution for Day19 {
+  /// The rules by ID, and the messages to validate against them
+  type Input = (HashMap<usize, Rule>, Vec<String>);
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    let mut it = input.lines();
+    let rules = it.by_ref().take_while(|s| !s.is_empty())
+      .enumerate()
+      .map(|(i, l)| parse_rule(l).map_err(|e| AocError::parse(i + 1, l, e)))
+      .collect::<Result<HashMap<usize, Rule>, _>>()?;
+
+    Ok((rules, it.map(String::from).collect()))
+  }
 
-fn main() {
-  let path = Path::new(r"data/19-1.txt");
-  println!("Result of problem 1: {}", problem1(path));
-  println!("Result of problem 2: {}", problem2(path));
+  fn part1((rules, messages): &Self::Input) -> Result<usize, AocError>
+  {
+    let rule0 = get_rule(rules, 0)?;
+    Ok(messages.iter().map(|s| if match_rule(rule0, s, rules) { 1 } else { 0 }).sum())
+  }
+
+  fn part2((rules, messages): &Self::Input) -> Result<usize, AocError>
+  {
+    let mut rules = rules.clone();
+    rules.insert(8, Rule::Alternative(vec![Rule::Sequence(vec![Rule::Reference(42), Rule::Reference(8)]), Rule::Reference(42)]));
+    rules.insert(11, Rule::Alternative(
+      vec![Rule::Sequence(vec![Rule::Reference(42), Rule::Reference(11), Rule::Reference(31)]),
+           Rule::Sequence(vec![Rule::Reference(42), Rule::Reference(31)])]));
+
+    let rule42 = get_rule(&rules, 42)?;
+    let rule31 = get_rule(&rules, 31)?;
+    Ok(messages.iter().map(|s| if match_two_rules(rule42, rule31, s, &rules) { 1 } else { 0 }).sum())
+  }
 }
 
 #[cfg(test)]
@@ -182,12 +190,15 @@ mod tests
   #[test]
   fn test_parse() {
     assert_eq!(parse_rule("0: 4 1 5"),
-               (0usize, Rule::Sequence(vec![Rule::Reference(4), Rule::Reference(1), Rule::Reference(5)])));
+               Ok((0usize, Rule::Sequence(vec![Rule::Reference(4), Rule::Reference(1), Rule::Reference(5)]))));
 
     assert_eq!(parse_rule("3: 4 | 1 5"),
-               (3usize, Rule::Alternative(vec![
+               Ok((3usize, Rule::Alternative(vec![
                  Rule::Reference(4),
-                 Rule::Sequence(vec![Rule::Reference(1), Rule::Reference(5)])])));
+                 Rule::Sequence(vec![Rule::Reference(1), Rule::Reference(5)])]))));
+
+    assert!(parse_rule("4 \"a\"").is_err());
+    assert!(parse_rule("4: b").is_err());
   }
 
   #[test]
@@ -199,7 +210,7 @@ mod tests
                      "4: \"a\"",
                      "5: \"b\""]
       .into_iter()
-      .map(|s| parse_rule(s))
+      .map(|l| parse_rule(l).unwrap())
       .collect::<HashMap<usize, Rule>>();
     let expanded = expand_rules(&rules);
 
@@ -234,15 +245,15 @@ mod tests
                      "4: \"a\"",
                      "5: \"b\""]
       .into_iter()
-      .map(|s| parse_rule(s))
+      .map(|l| parse_rule(l).unwrap())
       .collect::<HashMap<usize, Rule>>();
     let rule0 = rules.get(&0).unwrap();
 
-    assert!(match_rule(&rule0, "ababbb", &rules));
-    assert!(!match_rule(&rule0, "bababa", &rules));
-    assert!(match_rule(&rule0, "abbbab", &rules));
-    assert!(!match_rule(&rule0, "aaabbb", &rules));
-    assert!(!match_rule(&rule0, "aaaabbb", &rules));
+    assert!(match_rule(rule0, "ababbb", &rules));
+    assert!(!match_rule(rule0, "bababa", &rules));
+    assert!(match_rule(rule0, "abbbab", &rules));
+    assert!(!match_rule(rule0, "aaabbb", &rules));
+    assert!(!match_rule(rule0, "aaaabbb", &rules));
   }
 
   #[test]
@@ -280,7 +291,7 @@ mod tests
                      "7: 14 5 | 1 21",
                      "24: 14 1"]
       .into_iter()
-      .map(|s| parse_rule(s))
+      .map(|l| parse_rule(l).unwrap())
       .collect::<HashMap<usize, Rule>>();
     let rule42 = rules.get(&42).unwrap();
     let rule31 = rules.get(&31).unwrap();
@@ -301,7 +312,7 @@ mod tests
                              "babaaabbbaaabaababbaabababaaab",
                              "aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"]
       .into_iter()
-      .map(|s| if match_two_rules(&rule42, &rule31, s, &rules) { 1 } else { 0 })
+      .map(|s| if match_two_rules(rule42, rule31, s, &rules) { 1 } else { 0 })
       .sum();
     assert_eq!(result, 12);
   }
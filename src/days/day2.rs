@@ -0,0 +1,261 @@
+use std::fmt::{self, Display};
+use regex::Regex;
+use crate::error::AocError;
+use crate::utils::{describe_nom_error, parse_lines};
+use crate::Solution;
+
+use nom::{
+  IResult,
+  bytes::complete::take_while1,
+  character::complete::{char, digit1, satisfy, space0, space1},
+  combinator::{all_consuming, map_res, verify},
+  error::{context, VerboseError},
+  sequence::preceded,
+};
+
+/// A line of the password database, like "1-3 a: abcde"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AoC02PasswordEntry {
+  /// The first number of the policy, at least 1
+  pub min: usize,
+  /// The second number of the policy, at least `min`
+  pub max: usize,
+  pub character: char,
+  pub password: String,
+}
+
+type ParseResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+fn number(s: &str) -> ParseResult<'_, usize>
+{
+  map_res(digit1, str::parse)(s)
+}
+
+/// A whole entry, where the password is everything up to the end of the line
+fn entry(s: &str) -> ParseResult<'_, AoC02PasswordEntry>
+{
+  let (s, min) = preceded(space0, context("a minimum of at least 1", verify(number, |&n| n >= 1)))(s)?;
+  let (s, _) = context("\"-\"", char('-'))(s)?;
+  let (s, max) = context("a maximum no smaller than the minimum", verify(number, |&n| n >= min))(s)?;
+  let (s, character) = preceded(space1, context("a letter", satisfy(|c| !c.is_whitespace() && c != ':')))(s)?;
+  let (s, _) = context("\":\"", char(':'))(s)?;
+  let (s, password) = preceded(space0, context("a password", take_while1(|c: char| !c.is_whitespace())))(s)?;
+  let (s, _) = space0(s)?;
+  Ok((s, AoC02PasswordEntry { min, max, character, password: password.to_string() }))
+}
+
+impl AoC02PasswordEntry {
+  pub fn from_line(line: &str) -> Result<Self, String>
+  {
+    all_consuming(entry)(line).map(|(_, entry)| entry).map_err(|e| describe_nom_error(line, e))
+  }
+
+  /// How many times the character is in the password
+  fn count(&self) -> usize
+  {
+    self.password.chars().filter(|&c| c == self.character).count()
+  }
+
+  /// Whether the character is at each of the two positions in the password
+  fn at_positions(&self) -> Result<[bool; 2], String>
+  {
+    let at = |position: usize| self.password.chars().nth(position - 1)
+      .map(|c| c == self.character)
+      .ok_or_else(|| format!("position {} is past the end of the password", position));
+    Ok([at(self.min)?, at(self.max)?])
+  }
+}
+
+/// Written like "1-3 a: abcde"
+impl Display for AoC02PasswordEntry
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    write!(f, "{}-{} {}: {}", self.min, self.max, self.character, self.password)
+  }
+}
+
+/// A rule for which passwords are valid, given the policy written in front of them
+pub trait PasswordPolicy
+{
+  /// Check a password, and say why it's invalid if it is
+  fn check(&self, entry: &AoC02PasswordEntry) -> Result<(), String>;
+}
+
+/// The character appears between the first and second number of times (part 1)
+pub struct CountInRange;
+
+impl PasswordPolicy for CountInRange
+{
+  fn check(&self, entry: &AoC02PasswordEntry) -> Result<(), String>
+  {
+    let (min, max) = (entry.min, entry.max);
+    match entry.count() {
+      n if n < min || n > max => Err(format!("{:?} appears {} times, not {} to {}", entry.character, n, min, max)),
+      _ => Ok(())
+    }
+  }
+}
+
+/// The character is at exactly one of the two positions (part 2)
+pub struct OnePosition;
+
+impl PasswordPolicy for OnePosition
+{
+  fn check(&self, entry: &AoC02PasswordEntry) -> Result<(), String>
+  {
+    let (first, second) = (entry.min, entry.max);
+    match entry.at_positions()? {
+      [true, true] => Err(format!("{:?} is at both position {} and {}", entry.character, first, second)),
+      [false, false] => Err(format!("{:?} is at neither position {} nor {}", entry.character, first, second)),
+      _ => Ok(())
+    }
+  }
+}
+
+/// The character is at neither of the two positions
+pub struct NoneOfPositions;
+
+impl PasswordPolicy for NoneOfPositions
+{
+  fn check(&self, entry: &AoC02PasswordEntry) -> Result<(), String>
+  {
+    let (first, second) = (entry.min, entry.max);
+    match entry.at_positions()? {
+      [true, _] => Err(format!("{:?} is at position {}", entry.character, first)),
+      [_, true] => Err(format!("{:?} is at position {}", entry.character, second)),
+      _ => Ok(())
+    }
+  }
+}
+
+/// The character appears at most a given number of times, whatever the entry says
+pub struct AtMost(pub usize);
+
+impl PasswordPolicy for AtMost
+{
+  fn check(&self, entry: &AoC02PasswordEntry) -> Result<(), String>
+  {
+    match entry.count() {
+      n if n > self.0 => Err(format!("{:?} appears {} times, more than {}", entry.character, n, self.0)),
+      _ => Ok(())
+    }
+  }
+}
+
+/// The password matches a regular expression
+pub struct Matches(pub Regex);
+
+impl PasswordPolicy for Matches
+{
+  fn check(&self, entry: &AoC02PasswordEntry) -> Result<(), String>
+  {
+    match self.0.is_match(&entry.password) {
+      true => Ok(()),
+      false => Err(format!("doesn't match /{}/", self.0))
+    }
+  }
+}
+
+/// The names of the built-in policies, as accepted by `policy`
+pub const POLICY_NAMES: [&str; 5] = ["count", "positions", "none-of-positions", "at-most:N", "regex:PATTERN"];
+
+/// Find a built-in policy by name, where the policies taking a parameter are written like "at-most:3"
+pub fn policy(name: &str) -> Result<Box<dyn PasswordPolicy>, String>
+{
+  let (name, parameter) = match name.split_once(':') {
+    Some((name, parameter)) => (name, Some(parameter)),
+    None => (name, None)
+  };
+  match (name, parameter) {
+    ("count", None) => Ok(Box::new(CountInRange)),
+    ("positions", None) => Ok(Box::new(OnePosition)),
+    ("none-of-positions", None) => Ok(Box::new(NoneOfPositions)),
+    ("at-most", Some(n)) => Ok(Box::new(AtMost(n.parse().map_err(|e| format!("Invalid count {:?}: {}", n, e))?))),
+    ("regex", Some(pattern)) => Ok(Box::new(Matches(Regex::new(pattern).map_err(|e| e.to_string())?))),
+    _ => Err(format!("Unknown policy {:?}, expected one of {}", name, POLICY_NAMES.join(", ")))
+  }
+}
+
+/// How many of the passwords are valid under the policy
+pub fn count_valid(entries: &[AoC02PasswordEntry], policy: &dyn PasswordPolicy) -> usize
+{
+  entries.iter().filter(|e| policy.check(e).is_ok()).count()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+  type Input = Vec<AoC02PasswordEntry>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    parse_lines(input, AoC02PasswordEntry::from_line)
+  }
+
+  fn part1(input: &Self::Input) -> Result<usize, AocError>
+  {
+    Ok(count_valid(input, &CountInRange))
+  }
+
+  fn part2(input: &Self::Input) -> Result<usize, AocError>
+  {
+    Ok(count_valid(input, &OnePosition))
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  fn check(policy_name: &str, line: &str) -> Result<(), String>
+  {
+    policy(policy_name).unwrap().check(&AoC02PasswordEntry::from_line(line).unwrap())
+  }
+
+  #[test]
+  fn test_policies()
+  {
+    assert_eq!(check("count", "1-3 a: abcde"), Ok(()));
+    assert_eq!(check("count", "1-3 b: cdefg"), Err("'b' appears 0 times, not 1 to 3".to_string()));
+    assert_eq!(check("count", "2-3 a: aaa"), Ok(()));
+    assert_eq!(check("positions", "2-9 c: ccccccccc"), Err("'c' is at both position 2 and 9".to_string()));
+    assert_eq!(check("positions", "2-9 c: cccc"), Err("position 9 is past the end of the password".to_string()));
+    assert_eq!(check("none-of-positions", "1-3 b: cdefg"), Ok(()));
+    assert_eq!(check("none-of-positions", "1-3 a: abcde"), Err("'a' is at position 1".to_string()));
+    assert_eq!(check("at-most:8", "2-9 c: ccccccccc"), Err("'c' appears 9 times, more than 8".to_string()));
+    assert_eq!(check("regex:^[a-e]+$", "1-3 a: abcde"), Ok(()));
+    assert_eq!(check("regex:^[a-e]+$", "1-3 b: cdefg"), Err("doesn't match /^[a-e]+$/".to_string()));
+  }
+
+  #[test]
+  fn test_parse()
+  {
+    let entry = AoC02PasswordEntry::from_line("1-3 a: ab-c:de").unwrap();
+    assert_eq!(entry, AoC02PasswordEntry { min: 1, max: 3, character: 'a', password: "ab-c:de".to_string() });
+    assert_eq!(entry.to_string(), "1-3 a: ab-c:de");
+
+    let error = |line| AoC02PasswordEntry::from_line(line).unwrap_err();
+    assert_eq!(error("3-1 a: abcde"), "Column 3: Expected a maximum no smaller than the minimum");
+    assert_eq!(error("0-1 a: abcde"), "Column 1: Expected a minimum of at least 1");
+    assert_eq!(error("1-3 a:"), "Column 7: Expected a password");
+    assert_eq!(error("1-3 a: "), "Column 8: Expected a password");
+    assert_eq!(error("1 a: abcde"), "Column 2: Expected \"-\"");
+    assert_eq!(error("1-3 a abcde"), "Column 6: Expected \":\"");
+    assert_eq!(error("1-3 a: abc de"), "Column 12: Unexpected \"de\"");
+    assert!(matches!(Day2::parse("1-3 a: abcde\n1-3 b:"), Err(AocError::Parse { line: 2, .. })));
+  }
+
+  #[test]
+  fn test_policy_names()
+  {
+    assert!(policy("at-most").is_err());
+    assert!(policy("at-most:x").is_err());
+    assert!(policy("regex:(").is_err());
+    assert!(policy("count:1").is_err());
+    assert!(policy("length").is_err());
+  }
+}
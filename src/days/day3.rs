@@ -0,0 +1,196 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use crate::error::AocError;
+use crate::grid::{Grid, ToChar};
+use crate::Solution;
+
+/// The map of the trees, which repeats to the left and right
+pub struct Forest {
+  trees: Grid<bool>,
+}
+
+impl Forest {
+  fn detect_tree(c: char) -> Result<bool, String> {
+    match c {
+      '#' => Ok(true),
+      '.' => Ok(false),
+      _ => Err(format!("Expected '#' or '.', found {:?}", c))
+    }
+  }
+
+  pub fn parse(input: &str) -> Result<Self, AocError>
+  {
+    Ok(Forest { trees: Grid::parse(input, Forest::detect_tree)? })
+  }
+
+  fn is_tree(&self, x: isize, y: usize) -> bool {
+    *self.trees.get_wrapping(x, y as isize)
+  }
+}
+
+/// The positions visited going down a slope from the top left, until below the bottom row. The
+/// x coordinate keeps going past the edges, to the left for a negative `delta_x`.
+///
+/// None if `delta_y` is 0, as the slope would never leave the top row.
+fn path(forest: &Forest, delta_x: isize, delta_y: usize) -> Option<impl Iterator<Item=(isize, usize)>>
+{
+  if delta_y == 0 {
+    return None;
+  }
+  // The last step may land on the bottom rows without there being room for a whole step after it
+  let steps = forest.trees.height().div_ceil(delta_y);
+  Some((0..steps).map(move |i| (delta_x * i as isize, delta_y * i)))
+}
+
+/// How many trees are hit going down a slope, moving `delta_x` to the right (or left, if negative)
+/// and `delta_y` down every step, or None if `delta_y` is 0
+pub fn trees_for_slope(forest: &Forest, delta_x: isize, delta_y: usize) -> Option<usize> {
+  Some(path(forest, delta_x, delta_y)?
+    .filter(|&(x, y)| forest.is_tree(x, y))
+    .count())
+}
+
+/// A slope, and how many trees going down it hits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlopeTrees
+{
+  pub delta_x: isize,
+  pub delta_y: usize,
+  pub trees: usize,
+}
+
+/// Count the trees for every slope going left or right at most `max_x` and down at most `max_y` per
+/// step, and return the slopes hitting the fewest and the most trees, the first one in order of
+/// (delta_y, delta_x) for ties.
+///
+/// Slopes in the same direction can hit different trees, e.g. (2, 2) only visits every other
+/// position of (1, 1), so every step is tried.
+pub fn extreme_slopes(forest: &Forest, max_x: usize, max_y: usize) -> Option<(SlopeTrees, SlopeTrees)>
+{
+  let slopes = (1..=max_y)
+    .flat_map(|delta_y| (-(max_x as isize)..=max_x as isize).map(move |delta_x| (delta_x, delta_y)))
+    .filter_map(|(delta_x, delta_y)| Some(SlopeTrees { delta_x, delta_y, trees: trees_for_slope(forest, delta_x, delta_y)? }))
+    .collect::<Vec<_>>();
+  let fewest = slopes.iter().min_by_key(|s| s.trees)?;
+  let most = slopes.iter().min_by_key(|s| Reverse(s.trees))?;
+  Some((*fewest, *most))
+}
+
+/// Draw the forest the way the puzzle does, repeated to the left or right as far as the path goes,
+/// with the positions visited going down a slope marked 'O' for open ground and 'X' for trees. None
+/// if `delta_y` is 0.
+pub fn render_path(forest: &Forest, delta_x: isize, delta_y: usize) -> Option<String>
+{
+  let visited = path(forest, delta_x, delta_y)?.collect::<HashSet<_>>();
+  let width = forest.trees.width() as isize;
+  let first = visited.iter().map(|(x, _)| x.div_euclid(width)).min().unwrap_or(0);
+  let last = visited.iter().map(|(x, _)| x.div_euclid(width)).max().unwrap_or(0);
+  let map = Grid::from_fn(((last - first + 1) * width) as usize, forest.trees.height(), |x, y| (x as isize + first * width, y));
+  Some(map.render(|_, &(x, y)| match (visited.contains(&(x, y)), forest.is_tree(x, y)) {
+    (true, true) => 'X',
+    (true, false) => 'O',
+    (false, tree) => tree.to_char()
+  }))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+  type Input = Forest;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    Forest::parse(input)
+  }
+
+  fn part1(forest: &Forest) -> Result<usize, AocError>
+  {
+    Ok(trees_for_slope(forest, 3, 1).expect("The slope goes down"))
+  }
+
+  fn part2(forest: &Forest) -> Result<usize, AocError>
+  {
+    Ok([(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)].iter()
+      .map(|(x, y)| trees_for_slope(forest, *x, *y))
+      .product::<Option<usize>>()
+      .expect("The slopes go down"))
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  const FOREST: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+  #[test]
+  fn test_extreme_slopes()
+  {
+    let forest = Forest::parse(FOREST).unwrap();
+    let (fewest, most) = extreme_slopes(&forest, 7, 2).unwrap();
+    assert_eq!(fewest, SlopeTrees { delta_x: -6, delta_y: 2, trees: 0 });
+    assert_eq!(most, SlopeTrees { delta_x: 3, delta_y: 1, trees: 7 });
+    assert_eq!(extreme_slopes(&forest, 3, 0), None);
+
+    // (2, 2) skips the tree (1, 1) hits, and is the only slope missing every tree
+    let forest = Forest::parse(".....\n#####\n##.##\n.....\n.....").unwrap();
+    assert_eq!(trees_for_slope(&forest, 1, 1), Some(1));
+    assert_eq!(extreme_slopes(&forest, 2, 2).unwrap().0, SlopeTrees { delta_x: 2, delta_y: 2, trees: 0 });
+  }
+
+  #[test]
+  fn test_render_path()
+  {
+    let forest = Forest::parse(FOREST).unwrap();
+    let rendered = render_path(&forest, 3, 1).unwrap();
+    let lines = rendered.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines[0], "O.##.........##.........##.......");
+    assert_eq!(lines[1], "#..O#...#..#...#...#..#...#...#..");
+    assert_eq!(lines[2], ".#....X..#..#....#..#..#....#..#.");
+    assert_eq!(lines[10], ".#..#...#.#.#..#...#.#.#..#...X.#");
+
+    let rendered = render_path(&forest, -1, 4).unwrap();
+    assert_eq!(rendered.lines().map(|l| l.len()).collect::<Vec<_>>(), vec![22; 11]);
+    assert_eq!(rendered.lines().step_by(4).collect::<Vec<_>>(), vec![
+      "..##.......O.##.......",
+      ".#...##..#O.#...##..#.",
+      "#.##...#.O.#.##...#...",
+    ]);
+  }
+
+  #[test]
+  fn test_trees_for_slope()
+  {
+    let forest = Forest::parse(FOREST).unwrap();
+    assert_eq!(trees_for_slope(&forest, 3, 1), Some(7));
+    // 11 rows, so going down 2 at a time the last step lands on the bottom row
+    assert_eq!(path(&forest, 1, 2).unwrap().last(), Some((5, 10)));
+    assert_eq!(path(&forest, 1, 3).unwrap().last(), Some((3, 9)));
+    assert_eq!(path(&forest, -3, 1).unwrap().nth(4), Some((-12, 4)));
+    // Going left wraps around to the right edge: (-1, 1) is (10, 1), (-2, 2) is (9, 2) and so on
+    assert_eq!(trees_for_slope(&forest, -1, 1), Some(5));
+
+    let forest = Forest::parse("#..\n..#\n.#.").unwrap();
+    assert_eq!(trees_for_slope(&forest, 1, 1), Some(1));
+    assert_eq!(trees_for_slope(&forest, -1, 1), Some(3));
+    assert_eq!(trees_for_slope(&forest, 2, 2), Some(1));
+    assert_eq!(trees_for_slope(&forest, 1, 0), None);
+    assert!(render_path(&forest, 1, 0).is_none());
+
+    assert!(matches!(Forest::parse("..#\n.#\n#.."), Err(AocError::Parse { line: 2, .. })));
+  }
+}
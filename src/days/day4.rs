@@ -1,9 +1,11 @@
-use std::path::Path;
-use AoC2020::utils::EntryIterator;
 use std::collections::{HashMap, HashSet};
+use std::io;
+use crate::error::AocError;
+use crate::utils::EntryIterator;
 use regex::Regex;
+use crate::Solution;
 
-
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Passport {
   ecl: String,
@@ -19,16 +21,13 @@ struct Passport {
 impl Passport {
   fn validate_ecl(str: &str) -> Option<bool>
   {
-    Some(match str {
-      "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth" => true,
-      _ => false
-    })
+    Some(matches!(str, "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth"))
   }
 
   fn validate_year(str: &str, max: usize, min: usize) -> Option<bool>
   {
     Some(match str.parse::<usize>().ok() {
-      Some(num) => (num <= max && num >= min),
+      Some(num) => num <= max && num >= min,
       _ => false
     })
   }
@@ -60,8 +59,8 @@ impl Passport {
     let unit = caps.get(2)?.as_str();
 
     Some(match unit {
-      "cm" if num >= 150 && num <= 193 => true,
-      "in" if num >= 59 && num <= 76 => true,
+      "cm" if (150..=193).contains(&num) => true,
+      "in" if (59..=76).contains(&num) => true,
       _ => false
     })
   }
@@ -73,22 +72,17 @@ impl Passport {
 
   fn validate_entry(key: &str, value: &str) -> bool {
     let result = match key {
-      "ecl" => Self::validate_ecl(&value),
-      "byr" => Self::validate_byr(&value),
-      "iyr" => Self::validate_iyr(&value),
-      "eyr" => Self::validate_eyr(&value),
-      "hgt" => Self::validate_hgt(&value),
-      "hcl" => Self::validate_hcl(&value),
-      "pid" => Self::validate_pid(&value),
+      "ecl" => Self::validate_ecl(value),
+      "byr" => Self::validate_byr(value),
+      "iyr" => Self::validate_iyr(value),
+      "eyr" => Self::validate_eyr(value),
+      "hgt" => Self::validate_hgt(value),
+      "hcl" => Self::validate_hcl(value),
+      "pid" => Self::validate_pid(value),
       "cid" => Some(true),
       _ => Some(false)
     };
-    if let Some(result) = result
-    {
-      result
-    } else {
-      false
-    }
+    result.unwrap_or(false)
   }
 
   fn extract_entries(line: &str) -> Vec<(String, String)>
@@ -146,28 +140,26 @@ impl Passport {
   }
 }
 
-pub fn problem1(path: &Path) -> usize {
-  // let mut it = EntryIterator::new(path);
-  // let string = it.next().unwrap();
-  // println!("{}", string);
-  // let string = it.next().unwrap();
-  // println!("{}", string);
-  // let _test = Passport::new(&string);
-  // let passport = _test.unwrap();
-  // println!("{:?}", passport);
-  // passport.byr.len()
-  EntryIterator::new(path)
-    .map(|line| Passport::new(&line)).filter(|p| p.is_some()).count()
-}
+pub struct Day4;
 
-pub fn problem2(path: &Path) -> usize
-{
-  EntryIterator::new(path)
-    .map(|line| Passport::new_validated(&line)).filter(|p| p.is_some()).count()
-}
+impl Solution for Day4 {
+  type Input = Vec<String>;
+  type Output1 = usize;
+  type Output2 = usize;
 
-fn main() {
-  let path = Path::new(r"data/4-1.txt");
-  println!("Result of problem 1: {}", problem1(path));
-  println!("Result of problem 2: {}", problem2(path));
+  /// Passports span multiple lines and are separated by empty lines, join them into one line each
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    Ok(EntryIterator::from_string(input).collect::<io::Result<_>>()?)
+  }
+
+  fn part1(input: &Self::Input) -> Result<usize, AocError>
+  {
+    Ok(input.iter().filter_map(|line| Passport::new(line)).count())
+  }
+
+  fn part2(input: &Self::Input) -> Result<usize, AocError>
+  {
+    Ok(input.iter().filter_map(|line| Passport::new_validated(line)).count())
+  }
 }
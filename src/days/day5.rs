@@ -0,0 +1,134 @@
+use crate::error::AocError;
+use crate::utils::parse_lines;
+use crate::Solution;
+use std::cmp::{max, Ordering};
+
+/// These "boarding pass numbers" are actually just binary numbers.
+/// Both B and R represent 1s, F and L represent 0.
+/// Ensuring that these are in the correct places should be done before calling this.
+fn bp_str_to_num(bp_str: &str) -> Option<u16>
+{
+  if bp_str.len() > 16 {
+    return None;
+  }
+  let mut num = 0u16;
+  for c in bp_str.chars() {
+    num = (num << 1) + match c {
+      'B' | 'R' => 1,
+      'F' | 'L' => 0,
+      _ => return None
+    }
+  }
+  Some(num)
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Eq)]
+pub struct Seating {
+  row: u16,
+  col: u16,
+  seat_id: u16,
+}
+
+impl PartialEq for Seating {
+  fn eq(&self, other: &Self) -> bool {
+    self.seat_id == other.seat_id
+  }
+}
+
+impl PartialOrd for Seating
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Seating
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.seat_id.cmp(&other.seat_id)
+  }
+}
+
+fn decode_boarding_pass(str: &str) -> Result<Seating, String>
+{
+  let chars = str.chars().collect::<Vec<_>>();
+  if chars.len() != 10 {
+    return Err(format!("Expected 10 characters, found {}", chars.len()));
+  }
+  if let Some((i, c)) = chars.iter().enumerate().find(|&(i, &c)| !matches!((i < 7, c), (true, 'F' | 'B') | (false, 'L' | 'R'))) {
+    let expected = if i < 7 { "'F' or 'B'" } else { "'L' or 'R'" };
+    return Err(format!("Column {}: Expected {}, found {:?}", i + 1, expected, c));
+  }
+
+  let num = bp_str_to_num(str).ok_or("Invalid boarding pass")?;
+  Ok(Seating { row: (num >> 3), col: (num & 0x7), seat_id: num })
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+  type Input = Vec<Seating>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    parse_lines(input, decode_boarding_pass)
+  }
+
+  fn part1(passes: &Self::Input) -> Result<usize, AocError>
+  {
+    // Fairly easy, just parse the pseudo-binary code, then find the max value
+    Ok(passes.iter().fold(0, |acc, bp| max(acc, bp.seat_id as usize)))
+  }
+
+  fn part2(passes: &Self::Input) -> Result<usize, AocError>
+  {
+    // This one basically means to find the place where the sequence would stop in a sorted list of
+    // IDs, so create the sorted list of Seats
+    let mut passes = passes.iter().collect::<Vec<_>>();
+    passes.sort_unstable();
+
+    // Then reduce, keeping the last value that was in a valid sequence, and add 1 to it at the end
+    passes.into_iter()
+      .fold(None, |acc, bp| {
+        let seat_id = bp.seat_id as usize;
+        match acc {
+          None => Some(seat_id),
+          Some(previous) if previous + 1 == seat_id => Some(seat_id),
+          _ => acc
+        }
+      })
+      .map(|seat_id| seat_id + 1)
+      .ok_or(AocError::NoSolution)
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn bp_str_to_num_test()
+  {
+    assert_eq!(bp_str_to_num("FBFBBFFRLR"), Some(357));
+  }
+
+  #[test]
+  fn binary_space_partition()
+  {
+    assert_eq!(decode_boarding_pass("FBFBBFFFRLR"), Err("Expected 10 characters, found 11".to_string()));
+    assert_eq!(decode_boarding_pass("FBFBBFFFLR"), Err("Column 8: Expected 'L' or 'R', found 'F'".to_string()));
+    assert_eq!(decode_boarding_pass("FBFBBFRRLR"), Err("Column 7: Expected 'F' or 'B', found 'R'".to_string()));
+    assert_eq!(decode_boarding_pass("FFFFFFéLR"), Err("Expected 10 characters, found 9".to_string()));
+    assert_eq!(decode_boarding_pass("FFFFFFéLRR"), Err("Column 7: Expected 'F' or 'B', found 'é'".to_string()));
+    assert!(matches!(Day5::parse("FBFBBFFRLR\nFBFBBFFRL"), Err(AocError::Parse { line: 2, .. })));
+    assert_eq!(decode_boarding_pass("FBFBBFFRLR").unwrap(), Seating { row: 44, col: 5, seat_id: 357 });
+    assert_eq!(decode_boarding_pass("BFFFBBFRRR").unwrap(), Seating { row: 70, col: 7, seat_id: 567 });
+    assert_eq!(decode_boarding_pass("FFFBBBFRRR").unwrap(), Seating { row: 14, col: 7, seat_id: 119 });
+    assert_eq!(decode_boarding_pass("BBFFBBFRLL").unwrap(), Seating { row: 102, col: 4, seat_id: 820 });
+  }
+}
+
@@ -1,12 +1,14 @@
-use std::path::Path;
 use std::collections::BTreeSet;
-use AoC2020::utils::EntryIterator;
+use std::io;
+use crate::error::AocError;
+use crate::utils::EntryIterator;
+use crate::Solution;
 
 fn get_unique_answers(entry: &str) -> Option<String>
 {
   let set: BTreeSet<char> = entry.chars().filter(|&c| !c.is_whitespace()).collect();
 
-  if set.iter().all(|&x| x >= 'a' && x <= 'z') {
+  if set.iter().all(|&x| x.is_ascii_lowercase()) {
     Some(set.iter().collect())
   } else {
     None
@@ -27,28 +29,35 @@ fn get_universal_answers(entry: &str) -> Option<String>
     .collect())
 }
 
-fn problem1(path: &Path) -> usize
-{
-  EntryIterator::new(path)
-    .map(|s| get_unique_answers(&s))
-    .filter(|s| s.is_some())
-    .map(|s| s.unwrap().len())
-    .sum()
-}
+pub struct Day6;
 
-fn problem2(path: &Path) -> usize
-{
-  EntryIterator::new(path)
-    .map(|s| get_universal_answers(&s))
-    .filter(|s| s.is_some())
-    .map(|s| s.unwrap().len())
-    .sum()
-}
+impl Solution for Day6 {
+  type Input = Vec<String>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  /// Each group's answers span multiple lines and are separated by empty lines, join them into one
+  /// line per group
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    Ok(EntryIterator::from_string(input).collect::<io::Result<_>>()?)
+  }
+
+  fn part1(input: &Self::Input) -> Result<usize, AocError>
+  {
+    Ok(input.iter()
+      .filter_map(|s| get_unique_answers(s))
+      .map(|s| s.len())
+      .sum())
+  }
 
-fn main() {
-  let path = Path::new(r"data/6-1.txt");
-  println!("Result of problem 1: {}", problem1(path));
-  println!("Result of problem 2: {}", problem2(path));
+  fn part2(input: &Self::Input) -> Result<usize, AocError>
+  {
+    Ok(input.iter()
+      .filter_map(|s| get_universal_answers(s))
+      .map(|s| s.len())
+      .sum())
+  }
 }
 
 #[cfg(test)]
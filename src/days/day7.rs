@@ -0,0 +1,403 @@
+use std::collections::HashSet;
+use crate::error::AocError;
+use crate::graph::{Cycle, Direction, Graph, NodeId};
+use crate::utils::{describe_nom_error, parse_lines};
+use crate::Solution;
+
+use nom::{
+  IResult,
+  branch::alt,
+  bytes::complete::tag,
+  character::complete::{alpha1, char, digit1, space0, space1},
+  combinator::{all_consuming, map, map_res, opt, verify},
+  error::{context, VerboseError},
+  multi::separated_list1,
+  sequence::{preceded, tuple},
+};
+
+/// A rule saying that the bag on the left contains `weight` bags of the kind on the right
+#[derive(Clone, PartialEq, Debug)]
+struct BagEdge
+{
+  left: String,
+  right: String,
+  weight: usize,
+}
+
+/// Bags, with edges from each bag to the bags it must contain, weighted by how many it contains
+pub type BagGraph = Graph<String, usize>;
+
+type ParseResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// "bag" or "bags", after the words before it
+fn bag(s: &str) -> ParseResult<'_, &str>
+{
+  context("\"bag\" or \"bags\"", preceded(space1, alt((tag("bags"), tag("bag")))))(s)
+}
+
+/// One or more words naming a kind of bag, like "shiny gold"
+fn colour(s: &str) -> ParseResult<'_, String>
+{
+  let word = verify(alpha1, |w: &str| w != "bag" && w != "bags");
+  context("a bag colour", map(separated_list1(space1, word), |words| words.join(" ")))(s)
+}
+
+/// A count and a kind of bag, like "2 shiny gold bags"
+fn content(s: &str) -> ParseResult<'_, (usize, String)>
+{
+  context("a bag count and colour", tuple((
+    context("a bag count", map_res(digit1, str::parse)),
+    preceded(space1, colour),
+    bag,
+  )))(s)
+    .map(|(s, (count, colour, _))| (s, (count, colour)))
+}
+
+/// A whole rule, like "light red bags contain 1 bright white bag, 2 muted yellow bags."
+fn rule(s: &str) -> ParseResult<'_, (String, Vec<(usize, String)>)>
+{
+  let (s, container) = preceded(space0, colour)(s)?;
+  let (s, _) = tuple((bag, space1, context("\"contain\"", tag("contain")), space1))(s)?;
+  let (s, contents) = alt((
+    map(tuple((tag("no"), space1, tag("other"), bag)), |_| vec![]),
+    separated_list1(tuple((space0, char(','), space0)), content),
+  ))(s)?;
+  let (s, _) = tuple((space0, opt(char('.')), space0))(s)?;
+  Ok((s, (container, contents)))
+}
+
+/// The bag a rule is about, along with the edges to the bags it contains
+fn parse_edges(line: &str) -> Result<(String, Vec<BagEdge>), String>
+{
+  let (_, (left, contents)) = all_consuming(rule)(line).map_err(|e| describe_nom_error(line, e))?;
+  let edges = contents.into_iter().map(|(weight, right)| BagEdge { left: left.clone(), right, weight }).collect();
+  Ok((left, edges))
+}
+
+/// The bag rules, checked to be free of bags that end up containing themselves
+pub struct BagRules
+{
+  graph: BagGraph,
+  /// The total amount of bags inside each bag, by node ID
+  contained: Vec<usize>,
+}
+
+impl BagRules
+{
+  pub fn new(graph: BagGraph) -> Result<Self, AocError>
+  {
+    let contained = graph.path_product_sums(Direction::Outgoing)
+      .map_err(|cycle| AocError::invalid_input(format!("The bag rules contain a cycle: {}", describe_cycle(&graph, &cycle))))?;
+    Ok(BagRules { graph, contained })
+  }
+
+  /// Parse rules like "light red bags contain 1 bright white bag, 2 muted yellow bags.", one per line
+  pub fn parse(input: &str) -> Result<Self, AocError>
+  {
+    let mut graph = BagGraph::new();
+    for (left, edges) in parse_lines(input, parse_edges)?
+    {
+      // Bags that contain nothing still need a node, as nothing else might mention them
+      graph.add_node(left);
+      for e in edges {
+        graph.add_edge(e.left, e.right, e.weight);
+      }
+    }
+    BagRules::new(graph)
+  }
+
+  pub fn graph(&self) -> &BagGraph
+  {
+    &self.graph
+  }
+
+  /// The total amount of bags inside a bag, or None if no rule mentions it
+  pub fn contained(&self, bag: &str) -> Option<usize>
+  {
+    self.graph.id(bag).map(|id| self.contained[id])
+  }
+
+  /// The bags that can contain a bag, directly or indirectly, by name
+  pub fn ancestors(&self, bag: &str) -> Result<Vec<&str>, AocError>
+  {
+    let mut ancestors = self.graph.reachable(self.id(bag)?, Direction::Incoming).into_iter()
+      .map(|id| self.name(id))
+      .collect::<Vec<_>>();
+    ancestors.sort_unstable();
+    Ok(ancestors)
+  }
+
+  /// Every kind of bag inside a bag, along with how many of them there are in total, by name
+  pub fn contents(&self, bag: &str) -> Result<Vec<(&str, usize)>, AocError>
+  {
+    let mut contents = self.graph.path_products(self.id(bag)?).expect("The rules have no cycles")
+      .into_iter()
+      .map(|(id, count)| (self.name(id), count))
+      .collect::<Vec<_>>();
+    contents.sort_unstable();
+    Ok(contents)
+  }
+
+  /// The chain of bags with the fewest steps from the outer bag to the inner one, if the outer bag
+  /// can contain the inner one
+  pub fn shortest_path(&self, outer: &str, inner: &str) -> Result<Option<Vec<&str>>, AocError>
+  {
+    let path = self.graph.shortest_path(self.id(outer)?, self.id(inner)?);
+    Ok(path.map(|path| self.names(&path)))
+  }
+
+  /// The chain of bags from the outer bag to the inner one that holds the most inner bags, along with
+  /// how many it holds
+  pub fn heaviest_path(&self, outer: &str, inner: &str) -> Result<Option<(Vec<&str>, usize)>, AocError>
+  {
+    let path = self.graph.heaviest_path(self.id(outer)?, self.id(inner)?).expect("The rules have no cycles");
+    Ok(path.map(|(path, count)| (self.names(&path), count)))
+  }
+
+  /// The bags that aren't inside any other bag
+  pub fn roots(&self) -> Vec<&str>
+  {
+    let mut roots = self.graph.roots().map(|id| self.name(id)).collect::<Vec<_>>();
+    roots.sort_unstable();
+    roots
+  }
+
+  fn id(&self, bag: &str) -> Result<NodeId, AocError>
+  {
+    self.graph.id(bag).ok_or_else(|| AocError::invalid_input(format!("No rule mentions {} bags", bag)))
+  }
+
+  fn name(&self, id: NodeId) -> &str
+  {
+    self.graph.key(id)
+  }
+
+  fn names(&self, ids: &[NodeId]) -> Vec<&str>
+  {
+    ids.iter().map(|&id| self.name(id)).collect()
+  }
+
+  /// Draw the rules as a Graphviz graph, optionally highlighting a bag along with every bag that can
+  /// be reached from it in the given directions
+  pub fn to_dot(&self, highlight: Option<(&str, &[Direction])>) -> Result<String, AocError>
+  {
+    let mut highlighted = HashSet::new();
+    if let Some((bag, directions)) = highlight {
+      let id = self.id(bag)?;
+      highlighted.insert(id);
+      for &direction in directions {
+        highlighted.extend(self.graph.reachable(id, direction));
+      }
+    }
+    Ok(self.graph.to_dot("bags", &highlighted))
+  }
+}
+
+/// Describe a cycle like "a -> b -> a"
+fn describe_cycle(graph: &BagGraph, Cycle(cycle): &Cycle) -> String
+{
+  cycle.iter().chain(cycle.first())
+    .map(|&id| graph.key(id).as_str())
+    .collect::<Vec<_>>()
+    .join(" -> ")
+}
+
+/// The amount of different bags that can contain the start bag, directly or indirectly
+fn count_leaves_up(start: &str, rules: &BagRules) -> usize
+{
+  rules.graph.id(start).map_or(0, |id| rules.graph.reachable(id, Direction::Incoming).len())
+}
+
+/// The total amount of bags inside the start bag
+fn count_contained(start: &str, rules: &BagRules) -> usize
+{
+  rules.contained(start).unwrap_or(0)
+}
+
+const TARGET_BAG: &str = "shiny gold";
+
+/// Both parts are about shiny gold bags, so the rules have to mention them
+fn check_target(rules: &BagRules) -> Result<(), AocError>
+{
+  match rules.contained(TARGET_BAG) {
+    Some(_) => Ok(()),
+    None => Err(AocError::invalid_input(format!("No rule mentions {} bags", TARGET_BAG)))
+  }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+  type Input = BagRules;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    BagRules::parse(input)
+  }
+
+  fn part1(rules: &BagRules) -> Result<usize, AocError>
+  {
+    check_target(rules)?;
+    Ok(count_leaves_up(TARGET_BAG, rules))
+  }
+
+  fn part2(rules: &BagRules) -> Result<usize, AocError>
+  {
+    check_target(rules)?;
+    Ok(count_contained(TARGET_BAG, rules))
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn test_parse_edges()
+  {
+    let (left0, edges0) = parse_edges("faded blue bags contain no other bags.").unwrap();
+    let (_, edges1) = parse_edges("bright white bags contain 1 shiny gold bag.").unwrap();
+    let (_, edges2) = parse_edges("light red bags contain 1 bright white bag, 2 muted yellow bags.").unwrap();
+
+    assert_eq!(left0, "faded blue");
+    assert_eq!(edges0.len(), 0);
+    assert_eq!(edges1.len(), 1);
+    assert_eq!(edges2.len(), 2);
+
+    assert_eq!(edges1[0], BagEdge { left: "bright white".to_string(), right: "shiny gold".to_string(), weight: 1 });
+
+    assert_eq!(edges2[0], BagEdge { left: "light red".to_string(), right: "bright white".to_string(), weight: 1 });
+    assert_eq!(edges2[1], BagEdge { left: "light red".to_string(), right: "muted yellow".to_string(), weight: 2 });
+  }
+
+  #[test]
+  fn test_parse_edges_grammar()
+  {
+    let (_, edges) = parse_edges("  light red  bags contain 12 bright white bag ,3 muted yellow bags").unwrap();
+    assert_eq!(edges, vec![
+      BagEdge { left: "light red".to_string(), right: "bright white".to_string(), weight: 12 },
+      BagEdge { left: "light red".to_string(), right: "muted yellow".to_string(), weight: 3 },
+    ]);
+    assert_eq!(parse_edges("faded blue bag contain no other bags").unwrap(), ("faded blue".to_string(), vec![]));
+
+    assert_eq!(parse_edges("light red bags hold 1 bright white bag."), Err("Column 16: Expected \"contain\"".to_string()));
+    assert_eq!(parse_edges("light red bags contain one bright white bag."), Err("Column 24: Expected a bag count".to_string()));
+    assert_eq!(parse_edges("light red bags contain 1 bright white."), Err("Column 38: Expected \"bag\" or \"bags\"".to_string()));
+    assert_eq!(parse_edges("light red bags contain 1 bright white bag. 2"), Err("Column 44: Unexpected \"2\"".to_string()));
+  }
+
+  #[test]
+  fn test_count_leaves_up()
+  {
+    let text = r"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+    let rules = Day7::parse(text).unwrap();
+
+    assert_eq!(count_leaves_up("shiny gold", &rules), 4);
+  }
+
+  #[test]
+  fn test_count_contained()
+  {
+    let text = r"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+    let rules = Day7::parse(text).unwrap();
+
+    assert_eq!(count_contained("shiny gold", &rules), 32);
+
+    let text = r"shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+    let rules = Day7::parse(text).unwrap();
+
+    assert_eq!(count_contained("shiny gold", &rules), 126);
+  }
+
+  #[test]
+  fn test_cycle()
+  {
+    let text = r"shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags, 1 faded blue bag.
+dark orange bags contain 1 dark red bag.
+faded blue bags contain no other bags.";
+    match Day7::parse(text) {
+      Err(AocError::InvalidInput(reason)) => assert!(reason.ends_with("dark red -> dark orange -> dark red"), "{}", reason),
+      _ => panic!("Expected the cycle to be found")
+    }
+  }
+
+  #[test]
+  fn test_queries()
+  {
+    let text = r"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+    let rules = Day7::parse(text).unwrap();
+
+    assert_eq!(rules.ancestors("shiny gold").unwrap(), vec!["bright white", "dark orange", "light red", "muted yellow"]);
+    assert_eq!(rules.contents("shiny gold").unwrap(),
+               vec![("dark olive", 1), ("dotted black", 16), ("faded blue", 13), ("vibrant plum", 2)]);
+    assert_eq!(rules.shortest_path("light red", "faded blue").unwrap(), Some(vec!["light red", "muted yellow", "faded blue"]));
+    assert_eq!(rules.heaviest_path("light red", "dotted black").unwrap(),
+               Some((vec!["light red", "muted yellow", "shiny gold", "vibrant plum", "dotted black"], 48)));
+    assert_eq!(rules.shortest_path("faded blue", "shiny gold").unwrap(), None);
+    assert_eq!(rules.roots(), vec!["dark orange", "light red"]);
+    assert!(rules.ancestors("dark red").is_err());
+
+    // Rules without shiny gold bags can still be queried, but not solved
+    let rules = BagRules::parse("light red bags contain 2 dark blue bags.\ndark blue bags contain no other bags.").unwrap();
+    assert_eq!(rules.roots(), vec!["light red"]);
+    assert!(Day7::part1(&rules).is_err());
+    assert!(Day7::part2(&rules).is_err());
+
+    // A bag that contains nothing and is in nothing is still a bag
+    let rules = BagRules::parse("shiny gold bags contain no other bags.\nlight red bags contain 2 dark blue bags.").unwrap();
+    assert_eq!(rules.roots(), vec!["light red", "shiny gold"]);
+    assert_eq!(rules.contents("shiny gold").unwrap(), vec![]);
+    assert_eq!(Day7::part1(&rules).unwrap(), 0);
+    assert_eq!(Day7::part2(&rules).unwrap(), 0);
+  }
+
+  #[test]
+  fn test_to_dot()
+  {
+    let text = r"light red bags contain 1 shiny gold bag, 2 muted yellow bags.
+shiny gold bags contain 3 faded blue bags.
+faded blue bags contain no other bags.";
+    let rules = Day7::parse(text).unwrap();
+
+    let dot = rules.to_dot(Some(("shiny gold", &[Direction::Incoming]))).unwrap();
+    assert!(dot.contains(r#"[label="light red", style=filled"#));
+    assert!(dot.contains(r#"[label="shiny gold", style=filled"#));
+    assert!(dot.contains(r#"[label="faded blue"];"#));
+    assert!(dot.contains(r#"[label="3"];"#));
+    assert!(rules.to_dot(Some(("dark red", &[]))).is_err());
+  }
+}
@@ -0,0 +1,40 @@
+use crate::error::AocError;
+use crate::vm::{Halt, Instruction, LoopDetection, Machine};
+use crate::vm::asm::assemble;
+use crate::vm::cfg::find_patch;
+use crate::Solution;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+  type Input = Vec<Instruction>;
+  type Output1 = i32;
+  type Output2 = i32;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    assemble(input)
+  }
+
+  fn part1(instructions: &Self::Input) -> Result<i32, AocError>
+  {
+    let mut machine = Machine::new(instructions.clone()).halt_on(LoopDetection::new());
+    match machine.run() {
+      Halt::Loop => Ok(machine.state().acc()),
+      _ => Err(AocError::NoSolution)
+    }
+  }
+
+  fn part2(instructions: &Self::Input) -> Result<i32, AocError>
+  {
+    let (address, patched) = find_patch(instructions).ok_or(AocError::NoSolution)?;
+    let mut instructions = instructions.clone();
+    instructions[address] = patched;
+
+    let mut machine = Machine::new(instructions).halt_on(LoopDetection::new());
+    match machine.run() {
+      Halt::Terminated => Ok(machine.state().acc()),
+      _ => Err(AocError::NoSolution)
+    }
+  }
+}
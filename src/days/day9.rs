@@ -1,8 +1,9 @@
 use std::collections::{HashSet, VecDeque};
-use std::path::Path;
-use AoC2020::utils::read_lines;
 use std::borrow::Borrow;
 use std::cmp::{max,min};
+use crate::error::AocError;
+use crate::utils::parse_lines;
+use crate::Solution;
 
 struct ValidNumberCollector
 {
@@ -43,76 +44,81 @@ impl ValidNumberCollector
     self.contained_numbers.pop_front();
     self.contained_numbers.push_back(num);
     self.contained_numbers.make_contiguous();
-    self.valid_sums = Self::create_valid_sums(&self.contained_numbers.as_slices().0)
+    self.valid_sums = Self::create_valid_sums(self.contained_numbers.as_slices().0)
   }
 }
 
-fn find_invalid_number<'a, U, I>(n: usize, lines: I) -> usize
+fn find_invalid_number<U, I>(n: usize, lines: I) -> Option<usize>
   where U: Borrow<usize>,
         I: Iterator<Item=U>
 {
   let mut it = lines.map(|l|*l.borrow());
   let initial: Vec<usize> = it.by_ref().take(n).collect();
+  if initial.len() < n {
+    return None;
+  }
   let mut collector = ValidNumberCollector::new(&initial);
-  assert_eq!(initial.len(), n);
 
   it.find(|&x| {
       let is_valid = collector.is_number_valid(x);
       collector.insert(x);
       !is_valid
-    }).unwrap_or(0)
+    })
 }
 
-fn problem1(path: &Path) -> usize
-{
-  find_invalid_number(25, read_lines(path).unwrap().map(|x| x.unwrap().parse::<usize>().unwrap()))
-}
+/// Each number must be the sum of two of the `PREAMBLE` numbers before it
+pub struct Day9<const PREAMBLE: usize = 25>;
 
-fn problem2(path: &Path) -> usize
-{
-  let numbers = read_lines(path)
-    .unwrap()
-    .map(|x| x.unwrap().parse::<usize>().unwrap())
-    .collect::<Vec<_>>();
-
-  let invalid = find_invalid_number(25, numbers.iter());
-
-  for (i,n) in numbers.iter().enumerate() {
-    let mut sum = *n;
-    let mut j = i+1;
-    let mut high = sum;
-    let mut low = sum;
-    while sum < invalid && j < numbers.len() {
-      let inner = numbers[j];
-      sum += inner;
-      high = max(high, inner);
-      low = min(low, inner);
-      j+=1
-    }
+impl<const PREAMBLE: usize> Solution for Day9<PREAMBLE> {
+  type Input = Vec<usize>;
+  type Output1 = usize;
+  type Output2 = usize;
 
-    if sum == invalid {
-      return low + high;
-    }
+  fn parse(input: &str) -> Result<Self::Input, AocError>
+  {
+    parse_lines(input, |x| x.parse::<usize>())
   }
 
-  0
-}
+  fn part1(numbers: &Self::Input) -> Result<usize, AocError>
+  {
+    find_invalid_number(PREAMBLE, numbers.iter()).ok_or(AocError::NoSolution)
+  }
+
+  fn part2(numbers: &Self::Input) -> Result<usize, AocError>
+  {
+    let invalid = find_invalid_number(PREAMBLE, numbers.iter()).ok_or(AocError::NoSolution)?;
+
+    for (i,n) in numbers.iter().enumerate() {
+      let mut sum = *n;
+      let mut j = i+1;
+      let mut high = sum;
+      let mut low = sum;
+      while sum < invalid && j < numbers.len() {
+        let inner = numbers[j];
+        sum += inner;
+        high = max(high, inner);
+        low = min(low, inner);
+        j+=1
+      }
 
-fn main() {
-  let path = Path::new(r"data/9-1.txt");
-  println!("Result of problem 1: {}", problem1(path));
-  println!("Result of problem 2: {}", problem2(path));
+      if sum == invalid {
+        return Ok(low + high);
+      }
+    }
+
+    Err(AocError::NoSolution)
+  }
 }
 
 #[cfg(test)]
 mod tests
 {
-  use crate::{ValidNumberCollector, find_invalid_number};
+  use super::*;
 
   #[test]
   fn test_collect_valid_numbers()
   {
-    let collector = ValidNumberCollector::new(&vec![3, 4, 8]);
+    let collector = ValidNumberCollector::new(&[3, 4, 8]);
 
     assert!(collector.is_number_valid(7));
     assert!(collector.is_number_valid(11));
@@ -126,7 +132,7 @@ mod tests
   #[test]
   fn test_insert_number()
   {
-    let mut collector = ValidNumberCollector::new(&vec![3, 4, 8]);
+    let mut collector = ValidNumberCollector::new(&[3, 4, 8]);
 
     collector.insert(5);
 
@@ -143,7 +149,7 @@ mod tests
   #[test]
   fn test_find_invalid_number()
   {
-    let nums: Vec<usize> = r"35
+    let nums = <Day9>::parse(r"35
 20
 15
 25
@@ -162,8 +168,9 @@ mod tests
 299
 277
 309
-576".split("\n").map(|n| n.parse::<usize>().unwrap()).collect();
+576").unwrap();
 
-    assert_eq!(find_invalid_number(5, nums.iter()), 127);
+    assert_eq!(find_invalid_number(5, nums.iter()), Some(127));
+    assert_eq!(find_invalid_number(5, nums[..3].iter()), None);
   }
 }
\ No newline at end of file
@@ -0,0 +1,126 @@
+use std::time::{Duration, Instant};
+use crate::error::AocError;
+use crate::Solution;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15_dict;
+pub mod day15_vec;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+
+/// The answer to one part of a puzzle, and how long it took to compute
+pub struct Answer
+{
+  pub part: u8,
+  pub value: String,
+  pub time: Duration,
+}
+
+/// The result of running a solution on an input
+pub struct Run
+{
+  pub parse_time: Duration,
+  pub answers: Vec<Answer>,
+}
+
+type Runner = fn(&str, &[u8]) -> Result<Run, AocError>;
+
+fn run<S: Solution>(input: &str, parts: &[u8]) -> Result<Run, AocError>
+{
+  let start = Instant::now();
+  let parsed = S::parse(input)?;
+  let parse_time = start.elapsed();
+
+  let answers = parts.iter()
+    .map(|&part| {
+      let start = Instant::now();
+      let value = match part {
+        1 => S::part1(&parsed)?.to_string(),
+        _ => S::part2(&parsed)?.to_string()
+      };
+      Ok(Answer { part, value, time: start.elapsed() })
+    })
+    .collect::<Result<_, AocError>>()?;
+
+  Ok(Run { parse_time, answers })
+}
+
+/// A registered puzzle solution that the runner can dispatch to.
+///
+/// Some days have more than one implementation, these are told apart by their variant name.
+pub struct Day
+{
+  pub number: u8,
+  pub variant: Option<&'static str>,
+  runner: Runner,
+}
+
+impl Day {
+  pub(crate) fn new<S: Solution>(number: u8) -> Self
+  {
+    Day { number, variant: None, runner: run::<S> }
+  }
+
+  /// The name used to select this day from the command line, e.g. "7" or "15-dict"
+  pub fn name(&self) -> String
+  {
+    match self.variant {
+      Some(variant) => format!("{}-{}", self.number, variant),
+      None => self.number.to_string()
+    }
+  }
+
+  /// Parse the input, then solve the given parts of the puzzle
+  pub fn run(&self, input: &str, parts: &[u8]) -> Result<Run, AocError>
+  {
+    (self.runner)(input, parts)
+  }
+}
+
+/// All the registered days, in order
+pub fn all() -> Vec<Day>
+{
+  vec![
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day3::Day3>(3),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day6::Day6>(6),
+    Day::new::<day7::Day7>(7),
+    Day::new::<day8::Day8>(8),
+    Day::new::<day9::Day9>(9),
+    Day::new::<day10::Day10>(10),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15_vec::Day15>(15),
+    Day { variant: Some("dict"), ..Day::new::<day15_dict::Day15>(15) },
+    Day::new::<day16::Day16>(16),
+    Day::new::<day17::Day17>(17),
+    Day::new::<day18::Day18>(18),
+    Day::new::<day19::Day19>(19),
+  ]
+}
+
+/// Look up a day by the name it's registered under
+pub fn find(name: &str) -> Option<Day>
+{
+  all().into_iter().find(|d| d.name() == name)
+}
@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Everything that can go wrong while reading an input and solving a puzzle
+#[derive(Debug)]
+pub enum AocError
+{
+  Io(io::Error),
+  /// There's no input file at the path an input was looked up at
+  MissingInput(PathBuf),
+  /// A line of the input could not be parsed. Line numbers start at 1.
+  Parse { line: usize, text: String, reason: String },
+  /// The input as a whole doesn't make sense, e.g. it's empty or a required part is missing
+  InvalidInput(String),
+  NoSolution,
+}
+
+impl AocError
+{
+  pub fn parse<R: fmt::Display>(line: usize, text: &str, reason: R) -> Self
+  {
+    AocError::Parse { line, text: text.to_string(), reason: reason.to_string() }
+  }
+
+  pub fn invalid_input<R: fmt::Display>(reason: R) -> Self
+  {
+    AocError::InvalidInput(reason.to_string())
+  }
+}
+
+impl fmt::Display for AocError
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match self {
+      AocError::Io(e) => write!(f, "Unable to read input: {}", e),
+      AocError::MissingInput(path) => write!(f, "No input found at {}", path.display()),
+      AocError::Parse { line, text, reason } => write!(f, "Unable to parse line {} ({}): {:?}", line, reason, text),
+      AocError::InvalidInput(reason) => write!(f, "Invalid input: {}", reason),
+      AocError::NoSolution => write!(f, "Unable to find a solution"),
+    }
+  }
+}
+
+impl Error for AocError
+{
+  fn source(&self) -> Option<&(dyn Error + 'static)>
+  {
+    match self {
+      AocError::Io(e) => Some(e),
+      _ => None
+    }
+  }
+}
+
+impl From<io::Error> for AocError
+{
+  fn from(e: io::Error) -> Self
+  {
+    AocError::Io(e)
+  }
+}
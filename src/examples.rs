@@ -0,0 +1,406 @@
+//! The example inputs from the puzzle texts, along with the answers given for them.
+//!
+//! Every example gets its own test, which runs it through all the registered solutions for its
+//! day. Examples that need a solution set up differently from the real puzzle, e.g. day 9's
+//! shorter preamble, name the solution to run them through instead.
+
+/// An example input and the expected answer for one part of a day's puzzle
+pub struct Example
+{
+  pub name: &'static str,
+  pub day: u8,
+  pub part: u8,
+  pub input: &'static str,
+  pub answer: &'static str,
+}
+
+macro_rules! examples {
+  (@days $day:literal) => {
+    crate::days::all().into_iter().filter(|d| d.number == $day).collect::<Vec<_>>()
+  };
+  (@days $day:literal, $solution:ty) => {
+    vec![crate::days::Day::new::<$solution>($day)]
+  };
+  ($($name:ident: day $day:literal part $part:literal $(with $solution:ty)?, $input:expr => $answer:literal;)*) => {
+    /// All the examples, in order of day and part
+    pub fn all() -> Vec<Example>
+    {
+      vec![
+        $(Example { name: stringify!($name), day: $day, part: $part, input: $input, answer: $answer },)*
+      ]
+    }
+
+    #[cfg(test)]
+    mod tests
+    {
+      use super::*;
+      use crate::days::Day;
+
+      /// Run an example through each of the given solutions and check the answer
+      fn check_example(days: Vec<Day>, day: u8, part: u8, input: &str, answer: &str)
+      {
+        assert!(!days.is_empty(), "No solution registered for day {}", day);
+
+        for d in days {
+          let run = d.run(input, &[part]).unwrap_or_else(|e| panic!("Day {} failed: {}", d.name(), e));
+          assert_eq!(run.answers[0].value, answer, "Wrong answer for day {} part {}", d.name(), part);
+        }
+      }
+
+      $(
+        #[test]
+        fn $name()
+        {
+          check_example(examples!(@days $day $(, $solution)?), $day, $part, $input, $answer);
+        }
+      )*
+    }
+  };
+}
+
+const DAY1: &str = "1721
+979
+366
+299
+675
+1456";
+
+const DAY2: &str = "1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+const DAY3: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+const DAY4: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+
+const DAY4_INVALID: &str = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007";
+
+const DAY4_VALID: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+const DAY5: &str = "FBFBBFFRLR
+BFFFBBFRRR
+FFFBBBFRRR
+BBFFBBFRLL";
+
+const DAY6: &str = "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+const DAY7: &str = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+const DAY7_DEEP: &str = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+const DAY8: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+const DAY9: &str = "35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+const DAY10_SMALL: &str = "16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
+
+const DAY10_LARGE: &str = "28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+const DAY11: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+const DAY12: &str = "F10
+N3
+F7
+R90
+F11";
+
+const DAY13: &str = "939
+7,13,x,x,59,x,31,19";
+
+const DAY14_PART1: &str = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+
+const DAY14_PART2: &str = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+const DAY16_PART1: &str = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+const DAY16_PART2: &str = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+const DAY17: &str = ".#.
+..#
+###";
+
+const DAY18: &str = "2 * 3 + (4 * 5)
+5 + (8 * 3 + 9 + 3 * 4 * 3)";
+
+const DAY19_SIMPLE: &str = r#"0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"#;
+
+const DAY19_LOOPING: &str = r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
+
+examples! {
+  day1_part1: day 1 part 1, DAY1 => "514579";
+  day1_part2: day 1 part 2, DAY1 => "241861950";
+  day2_part1: day 2 part 1, DAY2 => "2";
+  day2_part2: day 2 part 2, DAY2 => "1";
+  day3_part1: day 3 part 1, DAY3 => "7";
+  day3_part2: day 3 part 2, DAY3 => "336";
+  day4_part1: day 4 part 1, DAY4 => "2";
+  day4_part2_invalid: day 4 part 2, DAY4_INVALID => "0";
+  day4_part2_valid: day 4 part 2, DAY4_VALID => "4";
+  day5_part1: day 5 part 1, DAY5 => "820";
+  day6_part1: day 6 part 1, DAY6 => "11";
+  day6_part2: day 6 part 2, DAY6 => "6";
+  day7_part1: day 7 part 1, DAY7 => "4";
+  day7_part2: day 7 part 2, DAY7 => "32";
+  day7_part2_deep: day 7 part 2, DAY7_DEEP => "126";
+  day8_part1: day 8 part 1, DAY8 => "5";
+  day8_part2: day 8 part 2, DAY8 => "8";
+  day9_part1: day 9 part 1 with crate::days::day9::Day9<5>, DAY9 => "127";
+  day9_part2: day 9 part 2 with crate::days::day9::Day9<5>, DAY9 => "62";
+  day10_part1_small: day 10 part 1, DAY10_SMALL => "35";
+  day10_part1_large: day 10 part 1, DAY10_LARGE => "220";
+  day10_part2_small: day 10 part 2, DAY10_SMALL => "8";
+  day10_part2_large: day 10 part 2, DAY10_LARGE => "19208";
+  day11_part1: day 11 part 1, DAY11 => "37";
+  day11_part2: day 11 part 2, DAY11 => "26";
+  day12_part1: day 12 part 1, DAY12 => "25";
+  day12_part2: day 12 part 2, DAY12 => "286";
+  day13_part1: day 13 part 1, DAY13 => "295";
+  day13_part2: day 13 part 2, DAY13 => "1068781";
+  day14_part1: day 14 part 1, DAY14_PART1 => "165";
+  day14_part2: day 14 part 2, DAY14_PART2 => "208";
+  // Part 2 runs for 30 million turns, which the day 15 modules already test on their own
+  day15_part1: day 15 part 1, "0,3,6" => "436";
+  day16_part1: day 16 part 1, DAY16_PART1 => "71";
+  // There are no departure fields in the example, so the answer is the empty product
+  day16_part2: day 16 part 2, DAY16_PART2 => "1";
+  day17_part1: day 17 part 1, DAY17 => "112";
+  day17_part2: day 17 part 2, DAY17 => "848";
+  day18_part1: day 18 part 1, DAY18 => "463";
+  day18_part2: day 18 part 2, DAY18 => "1491";
+  day19_part1_simple: day 19 part 1, DAY19_SIMPLE => "2";
+  day19_part1_looping: day 19 part 1, DAY19_LOOPING => "3";
+  day19_part2_looping: day 19 part 2, DAY19_LOOPING => "12";
+}
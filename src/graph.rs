@@ -0,0 +1,537 @@
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::ops::{Add, Mul};
+
+/// Nodes are interned, and referred to by their index in the graph
+pub type NodeId = usize;
+
+/// Which way to follow the edges of a graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction
+{
+  /// From a node to its successors
+  Outgoing,
+  /// From a node to its predecessors
+  Incoming,
+}
+
+/// A directed graph with weighted edges.
+///
+/// Nodes are identified by keys of any hashable type, which are interned into `NodeId`s when they
+/// are added. Edges are kept in both a forward and a reverse adjacency list, so both directions are
+/// equally cheap to walk.
+#[derive(Debug, Clone)]
+pub struct Graph<N, W>
+{
+  keys: Vec<N>,
+  ids: HashMap<N, NodeId>,
+  outgoing: Vec<Vec<(NodeId, W)>>,
+  incoming: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<N, W> Default for Graph<N, W>
+  where N: Eq + Hash + Clone,
+        W: Clone
+{
+  fn default() -> Self
+  {
+    Self::new()
+  }
+}
+
+impl<N, W> Graph<N, W>
+  where N: Eq + Hash + Clone,
+        W: Clone
+{
+  pub fn new() -> Self
+  {
+    Graph { keys: vec![], ids: HashMap::new(), outgoing: vec![], incoming: vec![] }
+  }
+
+  /// Add a node if it's not already in the graph, and return its ID
+  pub fn add_node(&mut self, key: N) -> NodeId
+  {
+    if let Some(&id) = self.ids.get(&key) {
+      return id;
+    }
+
+    let id = self.keys.len();
+    self.ids.insert(key.clone(), id);
+    self.keys.push(key);
+    self.outgoing.push(vec![]);
+    self.incoming.push(vec![]);
+    id
+  }
+
+  /// Add an edge between two nodes, adding the nodes as needed. An existing edge gets its weight replaced.
+  pub fn add_edge(&mut self, from: N, to: N, weight: W) -> (NodeId, NodeId)
+  {
+    let from = self.add_node(from);
+    let to = self.add_node(to);
+
+    match self.outgoing[from].iter().position(|(n, _)| *n == to) {
+      Some(i) => {
+        self.outgoing[from][i].1 = weight.clone();
+        let j = self.incoming[to].iter().position(|(n, _)| *n == from).unwrap();
+        self.incoming[to][j].1 = weight;
+      }
+      None => {
+        self.outgoing[from].push((to, weight.clone()));
+        self.incoming[to].push((from, weight));
+      }
+    }
+    (from, to)
+  }
+}
+
+impl<N, W> Graph<N, W>
+  where N: Eq + Hash
+{
+  pub fn id<Q>(&self, key: &Q) -> Option<NodeId>
+    where N: Borrow<Q>,
+          Q: Hash + Eq + ?Sized
+  {
+    self.ids.get(key).copied()
+  }
+
+  pub fn key(&self, id: NodeId) -> &N
+  {
+    &self.keys[id]
+  }
+
+  pub fn len(&self) -> usize
+  {
+    self.keys.len()
+  }
+
+  pub fn is_empty(&self) -> bool
+  {
+    self.keys.is_empty()
+  }
+
+  pub fn nodes(&self) -> impl Iterator<Item=NodeId>
+  {
+    0..self.keys.len()
+  }
+
+  pub fn edge_count(&self) -> usize
+  {
+    self.outgoing.iter().map(Vec::len).sum()
+  }
+
+  pub fn edge(&self, from: NodeId, to: NodeId) -> Option<&W>
+  {
+    self.outgoing[from].iter().find(|(n, _)| *n == to).map(|(_, w)| w)
+  }
+
+  /// The nodes this node has edges to, along with the weights of those edges
+  pub fn successors(&self, id: NodeId) -> &[(NodeId, W)]
+  {
+    &self.outgoing[id]
+  }
+
+  /// The nodes that have edges to this node, along with the weights of those edges
+  pub fn predecessors(&self, id: NodeId) -> &[(NodeId, W)]
+  {
+    &self.incoming[id]
+  }
+
+  /// The neighbours of a node when following edges in the given direction
+  pub fn neighbours(&self, id: NodeId, direction: Direction) -> &[(NodeId, W)]
+  {
+    match direction {
+      Direction::Outgoing => self.successors(id),
+      Direction::Incoming => self.predecessors(id)
+    }
+  }
+
+  /// Visit every node reachable from the start node in breadth-first order, starting with the start node
+  pub fn bfs(&self, start: NodeId, direction: Direction) -> Traversal<'_, N, W>
+  {
+    Traversal::new(self, start, direction, Order::BreadthFirst)
+  }
+
+  /// Visit every node reachable from the start node in depth-first pre-order, starting with the start node
+  pub fn dfs(&self, start: NodeId, direction: Direction) -> Traversal<'_, N, W>
+  {
+    Traversal::new(self, start, direction, Order::DepthFirst)
+  }
+
+  /// All the nodes that can be reached from the start node, not counting the start node itself
+  /// unless it's part of a cycle
+  pub fn reachable(&self, start: NodeId, direction: Direction) -> HashSet<NodeId>
+  {
+    let mut reached = HashSet::new();
+    let mut pending = vec![start];
+    while let Some(id) = pending.pop() {
+      for &(n, _) in self.neighbours(id, direction) {
+        if reached.insert(n) {
+          pending.push(n);
+        }
+      }
+    }
+    reached
+  }
+
+  /// The nodes that no edges lead into
+  pub fn roots(&self) -> impl Iterator<Item=NodeId> + '_
+  {
+    self.nodes().filter(move |&id| self.incoming[id].is_empty())
+  }
+
+  /// The path from one node to another with the fewest edges, if there is any
+  pub fn shortest_path(&self, from: NodeId, to: NodeId) -> Option<Vec<NodeId>>
+  {
+    let mut previous = vec![None; self.len()];
+    let mut seen = vec![false; self.len()];
+    let mut pending = VecDeque::from(vec![from]);
+    seen[from] = true;
+
+    while let Some(id) = pending.pop_front() {
+      if id == to {
+        return Some(unwind_path(to, &previous));
+      }
+      for &(n, _) in self.successors(id) {
+        if !seen[n] {
+          seen[n] = true;
+          previous[n] = Some(id);
+          pending.push_back(n);
+        }
+      }
+    }
+    None
+  }
+
+  /// Order the nodes so that every edge goes from an earlier node to a later one, or find a cycle if
+  /// there is no such order
+  pub fn topological_order(&self) -> Result<Vec<NodeId>, Cycle>
+  {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State { New, Open, Done }
+
+    let mut state = vec![State::New; self.len()];
+    let mut order = Vec::with_capacity(self.len());
+    for root in self.nodes() {
+      if state[root] != State::New {
+        continue;
+      }
+
+      // Depth-first, with the index of the next successor to visit kept next to each open node
+      state[root] = State::Open;
+      let mut stack = vec![(root, 0)];
+      while let Some((id, next)) = stack.last_mut() {
+        match self.outgoing[*id].get(*next) {
+          Some(&(n, _)) => {
+            *next += 1;
+            match state[n] {
+              State::New => {
+                state[n] = State::Open;
+                stack.push((n, 0));
+              }
+              State::Open => {
+                let start = stack.iter().position(|&(id, _)| id == n).unwrap();
+                return Err(Cycle(stack[start..].iter().map(|&(id, _)| id).collect()));
+              }
+              State::Done => ()
+            }
+          }
+          None => {
+            state[*id] = State::Done;
+            order.push(*id);
+            stack.pop();
+          }
+        }
+      }
+    }
+
+    order.reverse();
+    Ok(order)
+  }
+
+  /// For every node, the sum over every path leading out from it of the product of the edge weights
+  /// along the path, or the cycle that makes those sums infinite.
+  ///
+  /// With weights as "contains this many of", this is the total amount of things contained. Every
+  /// node is visited once, in topological order, so shared sub-paths are only summed once.
+  pub fn path_product_sums(&self, direction: Direction) -> Result<Vec<W>, Cycle>
+    where W: Copy + Add<Output=W> + Mul<Output=W> + From<u8>
+  {
+    let mut order = self.topological_order()?;
+    if direction == Direction::Outgoing {
+      order.reverse();
+    }
+
+    let mut sums = vec![W::from(0); self.len()];
+    for id in order {
+      sums[id] = self.neighbours(id, direction).iter()
+        .fold(W::from(0), |sum, &(n, w)| sum + w * (W::from(1) + sums[n]));
+    }
+    Ok(sums)
+  }
+
+  /// For every node that can be reached from the start node, the sum over every path leading to it
+  /// of the product of the edge weights along the path, or a cycle if there is one in the graph.
+  ///
+  /// With weights as "contains this many of", this is how many of each thing the start node contains.
+  pub fn path_products(&self, start: NodeId) -> Result<HashMap<NodeId, W>, Cycle>
+    where W: Copy + Add<Output=W> + Mul<Output=W> + From<u8>
+  {
+    let mut products = vec![None; self.len()];
+    products[start] = Some(W::from(1));
+    for id in self.topological_order()? {
+      if let Some(product) = products[id] {
+        for &(n, w) in self.successors(id) {
+          products[n] = Some(products[n].map_or(product * w, |p| p + product * w));
+        }
+      }
+    }
+
+    products[start] = None;
+    Ok(products.into_iter().enumerate().filter_map(|(id, p)| Some((id, p?))).collect())
+  }
+
+  /// The path from one node to another with the largest product of edge weights along it, along with
+  /// that product, or a cycle if there is one in the graph
+  pub fn heaviest_path(&self, from: NodeId, to: NodeId) -> Result<Option<(Vec<NodeId>, W)>, Cycle>
+    where W: Copy + Mul<Output=W> + PartialOrd + From<u8>
+  {
+    let mut heaviest = vec![None; self.len()];
+    let mut previous = vec![None; self.len()];
+    heaviest[from] = Some(W::from(1));
+    for id in self.topological_order()? {
+      if let Some(product) = heaviest[id] {
+        for &(n, w) in self.successors(id) {
+          if !matches!(heaviest[n], Some(h) if h >= product * w) {
+            heaviest[n] = Some(product * w);
+            previous[n] = Some(id);
+          }
+        }
+      }
+    }
+
+    Ok(heaviest[to].map(|weight| (unwind_path(to, &previous), weight)))
+  }
+
+  /// Draw the graph in Graphviz DOT format, with edges labelled by their weights.
+  ///
+  /// The highlighted nodes are filled in, and so are the edges between them.
+  pub fn to_dot(&self, name: &str, highlighted: &HashSet<NodeId>) -> String
+    where N: Display,
+          W: Display
+  {
+    let mut dot = format!("digraph {:?} {{\n", name);
+    for id in self.nodes() {
+      let style = if highlighted.contains(&id) { ", style=filled, fillcolor=gold" } else { "" };
+      dot += &format!("  {} [label={:?}{}];\n", id, self.key(id).to_string(), style);
+    }
+    for from in self.nodes() {
+      for (to, weight) in self.successors(from) {
+        let style = if highlighted.contains(&from) && highlighted.contains(to) { ", color=goldenrod, penwidth=2" } else { "" };
+        dot += &format!("  {} -> {} [label={:?}{}];\n", from, to, weight.to_string(), style);
+      }
+    }
+    dot += "}\n";
+    dot
+  }
+}
+
+/// Follow the links to the previous node of each node back from the end of a path to its start
+fn unwind_path(end: NodeId, previous: &[Option<NodeId>]) -> Vec<NodeId>
+{
+  let mut path = vec![end];
+  while let Some(id) = previous[path[path.len() - 1]] {
+    path.push(id);
+  }
+  path.reverse();
+  path
+}
+
+/// Nodes that each have an edge to the next one, with the last one having an edge back to the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<NodeId>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Order
+{
+  BreadthFirst,
+  DepthFirst,
+}
+
+/// Iterator over the nodes of a graph in breadth- or depth-first order
+pub struct Traversal<'a, N, W>
+{
+  graph: &'a Graph<N, W>,
+  direction: Direction,
+  order: Order,
+  pending: VecDeque<NodeId>,
+  visited: Vec<bool>,
+}
+
+impl<'a, N, W> Traversal<'a, N, W>
+{
+  fn new(graph: &'a Graph<N, W>, start: NodeId, direction: Direction, order: Order) -> Self
+  {
+    Traversal { graph, direction, order, pending: vec![start].into(), visited: vec![false; graph.keys.len()] }
+  }
+}
+
+impl<'a, N, W> Iterator for Traversal<'a, N, W>
+  where N: Eq + Hash
+{
+  type Item = NodeId;
+
+  fn next(&mut self) -> Option<NodeId>
+  {
+    loop {
+      let id = match self.order {
+        Order::BreadthFirst => self.pending.pop_front()?,
+        Order::DepthFirst => self.pending.pop_back()?
+      };
+      if self.visited[id] {
+        continue;
+      }
+      self.visited[id] = true;
+
+      let neighbours = self.graph.neighbours(id, self.direction).iter().map(|(n, _)| *n);
+      match self.order {
+        Order::BreadthFirst => self.pending.extend(neighbours),
+        // Reversed, so the first neighbour ends up on top of the stack
+        Order::DepthFirst => self.pending.extend(neighbours.rev())
+      }
+      return Some(id);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  /// a -2-> b -3-> d
+  /// a -1-> c -4-> d
+  fn diamond() -> Graph<&'static str, usize>
+  {
+    let mut graph = Graph::new();
+    graph.add_edge("a", "b", 2);
+    graph.add_edge("a", "c", 1);
+    graph.add_edge("b", "d", 3);
+    graph.add_edge("c", "d", 4);
+    graph
+  }
+
+  #[test]
+  fn test_add_nodes_and_edges()
+  {
+    let mut graph = diamond();
+    assert_eq!(graph.len(), 4);
+    assert_eq!(graph.edge_count(), 4);
+    assert_eq!(graph.add_node("a"), graph.id("a").unwrap());
+    assert_eq!(graph.id("e"), None);
+
+    let (a, b, d) = (graph.id("a").unwrap(), graph.id("b").unwrap(), graph.id("d").unwrap());
+    assert_eq!(*graph.key(b), "b");
+    assert_eq!(graph.edge(a, b), Some(&2));
+    assert_eq!(graph.edge(b, a), None);
+    assert_eq!(graph.predecessors(d).iter().map(|(n, _)| *graph.key(*n)).collect::<Vec<_>>(), vec!["b", "c"]);
+
+    graph.add_edge("a", "b", 5);
+    assert_eq!(graph.edge_count(), 4);
+    assert_eq!(graph.edge(a, b), Some(&5));
+    assert_eq!(graph.predecessors(b), &[(a, 5)]);
+  }
+
+  #[test]
+  fn test_traversal()
+  {
+    let graph = diamond();
+    let keys = |it: Traversal<&'static str, usize>| it.map(|n| *graph.key(n)).collect::<Vec<_>>();
+    let (a, d) = (graph.id("a").unwrap(), graph.id("d").unwrap());
+
+    assert_eq!(keys(graph.bfs(a, Direction::Outgoing)), vec!["a", "b", "c", "d"]);
+    assert_eq!(keys(graph.dfs(a, Direction::Outgoing)), vec!["a", "b", "d", "c"]);
+    assert_eq!(keys(graph.bfs(d, Direction::Incoming)), vec!["d", "b", "c", "a"]);
+    assert_eq!(keys(graph.dfs(d, Direction::Outgoing)), vec!["d"]);
+  }
+
+  #[test]
+  fn test_reachable()
+  {
+    let graph = diamond();
+    let (a, b, c, d) = ("a", "b", "c", "d");
+    let ids = |keys: &[&str]| keys.iter().map(|k| graph.id(k).unwrap()).collect::<HashSet<_>>();
+
+    assert_eq!(graph.reachable(graph.id(a).unwrap(), Direction::Outgoing), ids(&[b, c, d]));
+    assert_eq!(graph.reachable(graph.id(b).unwrap(), Direction::Incoming), ids(&[a]));
+    assert_eq!(graph.reachable(graph.id(d).unwrap(), Direction::Outgoing), ids(&[]));
+  }
+
+  #[test]
+  fn test_topological_order()
+  {
+    let mut graph = diamond();
+    let order = graph.topological_order().unwrap();
+    let position = |key| order.iter().position(|&n| n == graph.id(key).unwrap()).unwrap();
+    assert_eq!(position("a"), 0);
+    assert!(position("b") < position("d") && position("c") < position("d"));
+
+    graph.add_edge("d", "c", 1);
+    let Cycle(cycle) = graph.topological_order().unwrap_err();
+    assert_eq!(cycle.iter().map(|&n| *graph.key(n)).collect::<Vec<_>>(), vec!["d", "c"]);
+
+    graph.add_edge("e", "e", 1);
+    assert!(graph.path_product_sums(Direction::Outgoing).is_err());
+  }
+
+  #[test]
+  fn test_path_product_sums()
+  {
+    let graph = diamond();
+    let outgoing = graph.path_product_sums(Direction::Outgoing).unwrap();
+    let incoming = graph.path_product_sums(Direction::Incoming).unwrap();
+    // 2 b's containing 3 d's each, and 1 c containing 4 d's
+    assert_eq!(outgoing[graph.id("a").unwrap()], 2 + 2 * 3 + 1 + 4);
+    assert_eq!(outgoing[graph.id("c").unwrap()], 4);
+    assert_eq!(outgoing[graph.id("d").unwrap()], 0);
+    assert_eq!(incoming[graph.id("d").unwrap()], 3 * (1 + 2) + 4 * (1 + 1));
+  }
+
+  #[test]
+  fn test_paths()
+  {
+    let mut graph = diamond();
+    graph.add_edge("a", "d", 5);
+    let (a, b, c, d) = (graph.id("a").unwrap(), graph.id("b").unwrap(), graph.id("c").unwrap(), graph.id("d").unwrap());
+
+    assert_eq!(graph.roots().collect::<Vec<_>>(), vec![a]);
+    assert_eq!(graph.shortest_path(a, d), Some(vec![a, d]));
+    assert_eq!(graph.shortest_path(b, b), Some(vec![b]));
+    assert_eq!(graph.shortest_path(d, a), None);
+    assert_eq!(graph.heaviest_path(a, d), Ok(Some((vec![a, b, d], 6))));
+    assert_eq!(graph.heaviest_path(c, b), Ok(None));
+
+    let products = graph.path_products(a).unwrap();
+    assert_eq!(products, vec![(b, 2), (c, 1), (d, 2 * 3 + 4 + 5)].into_iter().collect());
+    assert_eq!(products.values().sum::<usize>(), graph.path_product_sums(Direction::Outgoing).unwrap()[a]);
+  }
+
+  #[test]
+  fn test_to_dot()
+  {
+    let graph = diamond();
+    let highlighted = graph.reachable(graph.id("c").unwrap(), Direction::Outgoing);
+    assert_eq!(graph.to_dot("diamond", &highlighted), r#"digraph "diamond" {
+  0 [label="a"];
+  1 [label="b"];
+  2 [label="c"];
+  3 [label="d", style=filled, fillcolor=gold];
+  0 -> 1 [label="2"];
+  0 -> 2 [label="1"];
+  1 -> 3 [label="3"];
+  2 -> 3 [label="4"];
+}
+"#);
+  }
+}
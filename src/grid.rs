@@ -0,0 +1,358 @@
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use crate::error::AocError;
+
+/// The four orthogonal directions as (dx, dy), with y growing downwards
+pub const DIRECTIONS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// All eight directions as (dx, dy), including the diagonals, in reading order
+pub const DIRECTIONS8: [(isize, isize); 8] = [
+  (-1, -1), (0, -1), (1, -1),
+  (-1, 0), (1, 0),
+  (-1, 1), (0, 1), (1, 1),
+];
+
+/// A cell that can be drawn as a single char
+pub trait ToChar
+{
+  fn to_char(&self) -> char;
+}
+
+impl ToChar for char
+{
+  fn to_char(&self) -> char
+  {
+    *self
+  }
+}
+
+/// Drawn the way most puzzles draw them, '#' for set and '.' for unset
+impl ToChar for bool
+{
+  fn to_char(&self) -> char
+  {
+    if *self { '#' } else { '.' }
+  }
+}
+
+/// A rectangular 2D grid, stored row by row. (0, 0) is the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T>
+{
+  cells: Vec<T>,
+  width: usize,
+  height: usize,
+}
+
+impl<T> Grid<T>
+{
+  /// Create a grid from its cells in row order, or None if the grid has no columns or the cells
+  /// don't fill it exactly
+  pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self>
+  {
+    if width > 0 && cells.len() == width * height {
+      Some(Grid { cells, width, height })
+    } else {
+      None
+    }
+  }
+
+  /// Create a grid by calling `f` with the coordinates of every cell. Panics if `width` is 0.
+  pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where F: FnMut(usize, usize) -> T
+  {
+    assert!(width > 0, "A grid must have at least one column");
+    let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| f(x, y)).collect();
+    Grid { cells, width, height }
+  }
+
+  /// Parse a grid drawn with one char per cell and one line per row, mapping every char to a cell.
+  ///
+  /// All rows must be as wide as the first one.
+  pub fn parse<F, E>(input: &str, f: F) -> Result<Self, AocError>
+    where F: Fn(char) -> Result<T, E>,
+          E: Display
+  {
+    let mut cells = vec![];
+    let mut width = None;
+    let mut height = 0;
+
+    for (y, line) in input.lines().enumerate() {
+      let before = cells.len();
+      for (x, c) in line.chars().enumerate() {
+        cells.push(f(c).map_err(|e| AocError::parse(y + 1, line, format!("Column {}: {}", x + 1, e)))?);
+      }
+      let row_width = cells.len() - before;
+      match width {
+        None => width = Some(row_width),
+        Some(width) if width != row_width =>
+          return Err(AocError::parse(y + 1, line, format!("Expected a row of {} cells, found {}", width, row_width))),
+        _ => ()
+      }
+      height += 1;
+    }
+
+    match width {
+      Some(width) if width > 0 => Ok(Grid { cells, width, height }),
+      _ => Err(AocError::invalid_input("The grid is empty"))
+    }
+  }
+
+  pub fn width(&self) -> usize
+  {
+    self.width
+  }
+
+  pub fn height(&self) -> usize
+  {
+    self.height
+  }
+
+  pub fn contains(&self, x: isize, y: isize) -> bool
+  {
+    x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+  }
+
+  pub fn get(&self, x: usize, y: usize) -> Option<&T>
+  {
+    if x < self.width && y < self.height {
+      Some(&self.cells[y * self.width + x])
+    } else {
+      None
+    }
+  }
+
+  pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T>
+  {
+    if x < self.width && y < self.height {
+      Some(&mut self.cells[y * self.width + x])
+    } else {
+      None
+    }
+  }
+
+  /// Like `get`, but for coordinates that may have stepped off the top or left edge
+  pub fn get_signed(&self, x: isize, y: isize) -> Option<&T>
+  {
+    if self.contains(x, y) {
+      self.get(x as usize, y as usize)
+    } else {
+      None
+    }
+  }
+
+  /// Get a cell as if the grid repeated infinitely in every direction
+  pub fn get_wrapping(&self, x: isize, y: isize) -> &T
+  {
+    let x = x.rem_euclid(self.width as isize) as usize;
+    let y = y.rem_euclid(self.height as isize) as usize;
+    &self.cells[y * self.width + x]
+  }
+
+  pub fn row(&self, y: usize) -> &[T]
+  {
+    &self.cells[(y * self.width)..((y + 1) * self.width)]
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item=&[T]>
+  {
+    self.cells.chunks(self.width)
+  }
+
+  pub fn column(&self, x: usize) -> impl Iterator<Item=&T>
+  {
+    self.cells.iter().skip(x).step_by(self.width).take(if x < self.width { self.height } else { 0 })
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T>>
+  {
+    (0..self.width).map(move |x| self.column(x))
+  }
+
+  /// All the cells in row order
+  pub fn iter(&self) -> impl Iterator<Item=&T>
+  {
+    self.cells.iter()
+  }
+
+  /// All the cells in row order, along with their (x, y) position
+  pub fn indexed(&self) -> impl Iterator<Item=((usize, usize), &T)>
+  {
+    let width = self.width;
+    self.cells.iter().enumerate().map(move |(i, c)| ((i % width, i / width), c))
+  }
+
+  /// The cells one step away from (x, y) in each of the given directions, skipping those outside the grid
+  pub fn neighbours<'a>(&'a self, x: usize, y: usize, directions: &'a [(isize, isize)]) -> impl Iterator<Item=((usize, usize), &'a T)> + 'a
+  {
+    directions.iter()
+      .map(move |&(dx, dy)| (x as isize + dx, y as isize + dy))
+      .filter_map(move |(x, y)| Some(((x as usize, y as usize), self.get_signed(x, y)?)))
+  }
+
+  /// The orthogonally adjacent cells of (x, y)
+  pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item=((usize, usize), &T)>
+  {
+    self.neighbours(x, y, &DIRECTIONS4)
+  }
+
+  /// The adjacent cells of (x, y), including the diagonal ones
+  pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item=((usize, usize), &T)>
+  {
+    self.neighbours(x, y, &DIRECTIONS8)
+  }
+
+  /// The cells seen when looking from (x, y) in a direction, until the edge of the grid.
+  ///
+  /// (x, y) itself is not included.
+  pub fn ray(&self, x: usize, y: usize, (dx, dy): (isize, isize)) -> impl Iterator<Item=((usize, usize), &T)>
+  {
+    (1..)
+      .map(move |i| (x as isize + dx * i, y as isize + dy * i))
+      .take_while(move |&(x, y)| (dx, dy) != (0, 0) && self.contains(x, y))
+      .map(move |(x, y)| ((x as usize, y as usize), &self[(x as usize, y as usize)]))
+  }
+
+  /// Create a new grid of the same size by mapping every cell along with its position
+  pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where F: FnMut((usize, usize), &T) -> U
+  {
+    Grid { cells: self.indexed().map(|(p, c)| f(p, c)).collect(), width: self.width, height: self.height }
+  }
+
+  /// Draw the grid with one char per cell
+  pub fn render<F>(&self, f: F) -> String
+    where F: Fn((usize, usize), &T) -> char
+  {
+    let mut s = String::with_capacity((self.width + 1) * self.height);
+    for (i, ((x, y), c)) in self.indexed().enumerate() {
+      if i > 0 && x == 0 {
+        s.push('\n');
+      }
+      s.push(f((x, y), c));
+    }
+    s
+  }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T>
+{
+  type Output = T;
+
+  fn index(&self, (x, y): (usize, usize)) -> &T
+  {
+    self.get(x, y).expect("Position outside of the grid")
+  }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T>
+{
+  fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T
+  {
+    self.get_mut(x, y).expect("Position outside of the grid")
+  }
+}
+
+impl<T: ToChar> Display for Grid<T>
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    write!(f, "{}", self.render(|_, c| c.to_char()))
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  fn parse_bools(input: &str) -> Result<Grid<bool>, AocError>
+  {
+    Grid::parse(input, |c| match c {
+      '#' => Ok(true),
+      '.' => Ok(false),
+      _ => Err("Expected '#' or '.'")
+    })
+  }
+
+  #[test]
+  fn test_parse()
+  {
+    let grid = parse_bools("#..\n.#.").unwrap();
+    assert_eq!(grid, Grid::new(3, 2, vec![true, false, false, false, true, false]).unwrap());
+    assert_eq!(grid.to_string(), "#..\n.#.");
+
+    assert!(matches!(parse_bools("#..\n.#"), Err(AocError::Parse { line: 2, .. })));
+    assert!(matches!(parse_bools("#..\n.x."), Err(AocError::Parse { line: 2, .. })));
+    assert!(matches!(parse_bools(""), Err(AocError::InvalidInput(_))));
+  }
+
+  #[test]
+  fn test_new()
+  {
+    assert_eq!(Grid::new(2, 1, vec![1, 2]).map(|g| g.rows().count()), Some(1));
+    assert_eq!(Grid::new(2, 1, vec![1, 2, 3]), None);
+    assert_eq!(Grid::<u8>::new(0, 3, vec![]), None);
+  }
+
+  #[test]
+  #[should_panic(expected = "at least one column")]
+  fn test_from_fn_without_columns()
+  {
+    Grid::from_fn(0, 3, |x, y| x + y);
+  }
+
+  #[test]
+  fn test_access()
+  {
+    let grid = Grid::from_fn(3, 2, |x, y| x + 10 * y);
+    assert_eq!(grid.get(2, 1), Some(&12));
+    assert_eq!(grid.get(3, 1), None);
+    assert_eq!(grid.get_signed(-1, 0), None);
+    assert_eq!(grid[(1, 1)], 11);
+    assert_eq!(*grid.get_wrapping(4, -1), 11);
+    assert_eq!(*grid.get_wrapping(-3, 2), 0);
+  }
+
+  #[test]
+  fn test_rows_and_columns()
+  {
+    let grid = Grid::from_fn(3, 2, |x, y| x + 10 * y);
+    assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[0, 1, 2][..], &[10, 11, 12][..]]);
+    assert_eq!(grid.row(1), &[10, 11, 12]);
+    assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![1, 11]);
+    assert_eq!(grid.columns().map(|c| c.sum::<usize>()).collect::<Vec<_>>(), vec![10, 12, 14]);
+    assert_eq!(grid.column(3).count(), 0);
+  }
+
+  #[test]
+  fn test_neighbours()
+  {
+    let grid = Grid::from_fn(3, 3, |x, y| x + 10 * y);
+    let values = |it: &mut dyn Iterator<Item=((usize, usize), &usize)>| it.map(|(_, &v)| v).collect::<Vec<_>>();
+
+    assert_eq!(values(&mut grid.neighbours4(1, 1)), vec![1, 12, 21, 10]);
+    assert_eq!(values(&mut grid.neighbours4(0, 0)), vec![1, 10]);
+    assert_eq!(values(&mut grid.neighbours8(1, 1)), vec![0, 1, 2, 10, 12, 20, 21, 22]);
+    assert_eq!(values(&mut grid.neighbours8(2, 2)), vec![11, 12, 21]);
+  }
+
+  #[test]
+  fn test_ray()
+  {
+    let grid = Grid::from_fn(4, 4, |x, y| x + 10 * y);
+    assert_eq!(grid.ray(0, 0, (1, 1)).map(|(_, &v)| v).collect::<Vec<_>>(), vec![11, 22, 33]);
+    assert_eq!(grid.ray(2, 1, (-1, 0)).map(|(p, _)| p).collect::<Vec<_>>(), vec![(1, 1), (0, 1)]);
+    assert_eq!(grid.ray(0, 0, (0, -1)).count(), 0);
+    assert_eq!(grid.ray(0, 0, (0, 0)).count(), 0);
+  }
+
+  #[test]
+  fn test_map_and_render()
+  {
+    let grid = parse_bools("#.\n.#").unwrap();
+    let flipped = grid.map(|_, &b| !b);
+    assert_eq!(flipped.to_string(), ".#\n#.");
+    assert_eq!(grid.render(|(x, _), &b| if b { 'O' } else { char::from(b'0' + x as u8) }), "O1\n0O");
+  }
+}
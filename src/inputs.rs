@@ -0,0 +1,112 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::error::AocError;
+
+/// The environment variable that overrides where inputs are looked up
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+/// The name of our own puzzle input, used when no other input is asked for
+pub const DEFAULT_NAME: &str = "1";
+
+/// Looks up puzzle inputs by day in a data directory.
+///
+/// Inputs are stored as "<day>-<name>.txt", so a day can have several inputs next to our own, e.g.
+/// "17-1.txt" and "17-example.txt".
+pub struct InputStore
+{
+  dir: PathBuf,
+}
+
+impl InputStore
+{
+  pub fn new<P: Into<PathBuf>>(dir: P) -> Self
+  {
+    InputStore { dir: dir.into() }
+  }
+
+  /// Use the directory from `AOC_DATA_DIR` if it's set, otherwise "data" in the current directory if
+  /// there is one, and finally the data directory of this repository
+  pub fn from_env() -> Self
+  {
+    if let Some(dir) = env::var_os(DATA_DIR_VAR) {
+      return Self::new(dir);
+    }
+
+    let local = Path::new("data");
+    if local.is_dir() {
+      Self::new(local)
+    } else {
+      Self::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data"))
+    }
+  }
+
+  pub fn dir(&self) -> &Path
+  {
+    &self.dir
+  }
+
+  pub fn path(&self, day: u8, name: &str) -> PathBuf
+  {
+    self.dir.join(format!("{}-{}.txt", day, name))
+  }
+
+  pub fn read(&self, day: u8, name: &str) -> Result<String, AocError>
+  {
+    let path = self.path(day, name);
+    fs::read_to_string(&path).map_err(|e| match e.kind() {
+      io::ErrorKind::NotFound => AocError::MissingInput(path),
+      _ => e.into()
+    })
+  }
+
+  /// The names of all the inputs there are for a day, in sorted order
+  pub fn names(&self, day: u8) -> Result<Vec<String>, AocError>
+  {
+    let prefix = format!("{}-", day);
+    let mut names = vec![];
+    for entry in fs::read_dir(&self.dir)? {
+      let file_name = entry?.file_name();
+      if let Some(name) = file_name.to_str().and_then(|f| f.strip_prefix(&prefix)?.strip_suffix(".txt")) {
+        names.push(name.to_string());
+      }
+    }
+    names.sort();
+    Ok(names)
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn test_input_store()
+  {
+    let dir = env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for file in &["17-1.txt", "17-example.txt", "1-1.txt", "notes.md"] {
+      fs::write(dir.join(file), file).unwrap();
+    }
+
+    let store = InputStore::new(&dir);
+    assert_eq!(store.names(17).unwrap(), vec!["1", "example"]);
+    assert_eq!(store.names(1).unwrap(), vec!["1"]);
+    assert!(store.names(2).unwrap().is_empty());
+    assert_eq!(store.read(17, "example").unwrap(), "17-example.txt");
+    assert!(matches!(store.read(17, "missing"), Err(AocError::MissingInput(_))));
+
+    fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn test_repository_inputs()
+  {
+    let store = InputStore::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data"));
+    for day in 1..=19 {
+      assert!(store.names(day).unwrap().contains(&DEFAULT_NAME.to_string()), "Day {} has no input", day);
+    }
+  }
+}
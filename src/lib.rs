@@ -1 +1,29 @@
-pub mod utils;
\ No newline at end of file
+#![allow(non_snake_case)]
+
+use std::fmt::Display;
+use error::AocError;
+
+pub mod error;
+pub mod utils;
+pub mod days;
+pub mod examples;
+pub mod answers;
+pub mod bench;
+pub mod inputs;
+pub mod grid;
+pub mod graph;
+pub mod vm;
+
+/// A puzzle solution, split into parsing the input and solving the two parts of the puzzle.
+///
+/// The input is only parsed once, and the parsed form is shared between both parts.
+pub trait Solution
+{
+  type Input;
+  type Output1: Display;
+  type Output2: Display;
+
+  fn parse(input: &str) -> Result<Self::Input, AocError>;
+  fn part1(input: &Self::Input) -> Result<Self::Output1, AocError>;
+  fn part2(input: &Self::Input) -> Result<Self::Output2, AocError>;
+}
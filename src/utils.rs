@@ -1,68 +1,166 @@
-use std::io::{self, BufRead, Lines, BufReader};
+use std::io::{self, BufRead, Read, Lines, BufReader};
+use std::fmt::Display;
 use std::fs::File;
 use std::path::Path;
+use nom::error::{VerboseError, VerboseErrorKind};
+use crate::error::AocError;
 
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+/// Open an input for reading, where the path "-" means stdin
+pub fn open_input<P>(path: P) -> io::Result<Box<dyn BufRead>>
   where P: AsRef<Path>
 {
-  let file = File::open(filename)?;
-  Ok(io::BufReader::new(file).lines())
+  let path = path.as_ref();
+  if path == Path::new("-") {
+    Ok(Box::new(BufReader::new(io::stdin())))
+  } else {
+    Ok(Box::new(BufReader::new(File::open(path)?)))
+  }
 }
 
-pub fn read_numbers_from_lines<P>(filename: P) -> io::Result<impl Iterator<Item=isize>>
+/// Read a whole input into a string, where the path "-" means stdin
+pub fn read_input<P>(path: P) -> io::Result<String>
   where P: AsRef<Path>
 {
-  Ok(read_lines(filename)?.map(|l| {
-    let parse_result = l.expect("Unable to read line").trim().parse::<isize>();
-    match parse_result {
-      Ok(num) => num,
-      Err(_) => panic!("Unable to parse integer")
-    }
-  }))
+  let mut input = String::new();
+  open_input(path)?.read_to_string(&mut input)?;
+  Ok(input)
+}
+
+/// The lines of any reader, e.g. a file, stdin or a string through `as_bytes()`
+pub fn read_lines<R>(reader: R) -> Lines<R>
+  where R: BufRead
+{
+  reader.lines()
 }
 
-/// Iterator that reads from a file entries that may span over multiple lines and are separated by
-/// empty lines.
+pub fn read_numbers_from_lines<R>(reader: R) -> impl Iterator<Item=Result<isize, AocError>>
+  where R: BufRead
+{
+  read_lines(reader).enumerate().map(|(i, l)| {
+    let l = l?;
+    l.trim().parse::<isize>().map_err(|e| AocError::parse(i + 1, &l, e))
+  })
+}
+
+/// Parse every line of the input with the given function.
+///
+/// If parsing a line fails, the error will point out the line number and its contents.
+pub fn parse_lines<T, E, F>(input: &str, f: F) -> Result<Vec<T>, AocError>
+  where F: Fn(&str) -> Result<T, E>,
+        E: Display
+{
+  input
+    .lines()
+    .enumerate()
+    .map(|(i, l)| f(l).map_err(|e| AocError::parse(i + 1, l, e)))
+    .collect()
+}
+
+/// Iterator that reads entries that may span over multiple lines and are separated by empty lines.
 ///
 /// The iterator will return one entry at a time as a String, with each line in the original entry
-/// now separated by a custom separator.
-pub struct EntryIterator {
-  lines: Lines<BufReader<File>>,
+/// now separated by a custom separator. Any number of empty lines may separate two entries.
+pub struct EntryIterator<R: BufRead> {
+  lines: Lines<R>,
   separator: String
 }
 
-impl EntryIterator {
+impl<R: BufRead> EntryIterator<R> {
   /// Create an iterator with the default separator (a space)
-  pub fn new(path: &Path) -> Self {
-    Self::new_with_separator(path, " ")
+  pub fn new(reader: R) -> Self {
+    Self::new_with_separator(reader, " ")
+  }
+
+  pub fn new_with_separator(reader: R, separator: &str) -> Self
+  {
+    EntryIterator { lines: read_lines(reader), separator: separator.to_string() }
   }
+}
 
-  pub fn new_with_separator(path: &Path, separator: &str) -> Self
+impl<'a> EntryIterator<&'a [u8]> {
+  /// Iterate over the entries of an input that's already been read into memory
+  pub fn from_string(input: &'a str) -> Self
   {
-    EntryIterator { lines: read_lines(path).unwrap(), separator: separator.to_string() }
+    Self::new(input.as_bytes())
   }
 }
 
-impl Iterator for EntryIterator
+impl<R: BufRead> Iterator for EntryIterator<R>
 {
-  type Item = String;
+  type Item = io::Result<String>;
 
   fn next(&mut self) -> Option<Self::Item> {
-    let line = self.lines.by_ref()
-      .take_while(|x| x.is_ok() && x.as_ref().unwrap().trim().len() > 0)
-      .map(|s| s.unwrap())
-      .collect::<Vec<_>>()
-      .join(&self.separator);
-    match line.len() {
+    let mut entry = Vec::<String>::new();
+    for line in self.lines.by_ref() {
+      let line = match line {
+        Ok(line) => line,
+        Err(e) => return Some(Err(e))
+      };
+      if !line.trim().is_empty() {
+        entry.push(line);
+      } else if !entry.is_empty() {
+        break;
+      }
+    }
+    match entry.len() {
       0 => None,
-      _ => Some(line)
+      _ => Some(Ok(entry.join(&self.separator)))
     }
   }
 }
 
-pub fn split_in_two(s:&str, separator:&str) -> [String;2]
+/// Split a string around the first occurrence of the separator, if it contains it
+pub fn split_in_two(s:&str, separator:&str) -> Option<[String;2]>
 {
-  let mut s : Vec<String> = s.split(separator).take(2).map(|s|s.to_string()).collect();
-  let back = s.pop().unwrap();
-  [s.pop().unwrap(), back]
+  let mut s = s.splitn(2, separator).map(|s|s.to_string());
+  Some([s.next()?, s.next()?])
+}
+
+/// Describe where and why nom couldn't parse a line, like "Column 5: Expected a bag count", using the
+/// innermost context the parser gave
+pub fn describe_nom_error(line: &str, e: nom::Err<VerboseError<&str>>) -> String
+{
+  match e {
+    nom::Err::Error(e) | nom::Err::Failure(e) => {
+      let rest = e.errors.first().map_or("", |(rest, _)| rest);
+      let column = line[..(line.len() - rest.len())].chars().count() + 1;
+      // The innermost context is the most specific description of what was expected
+      let expected = e.errors.iter().find_map(|(_, kind)| match kind {
+        VerboseErrorKind::Context(expected) => Some(*expected),
+        _ => None
+      });
+      match expected {
+        Some(expected) => format!("Column {}: Expected {}", column, expected),
+        None => format!("Column {}: Unexpected {:?}", column, rest)
+      }
+    }
+    nom::Err::Incomplete(_) => "Incomplete line".to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn test_read_numbers_from_lines()
+  {
+    let numbers = read_numbers_from_lines("1\n-20\n 300 ".as_bytes()).collect::<Result<Vec<_>, _>>();
+    assert_eq!(numbers.unwrap(), vec![1, -20, 300]);
+
+    let mut numbers = read_numbers_from_lines("1\ntwo".as_bytes());
+    assert!(numbers.next().unwrap().is_ok());
+    assert!(matches!(numbers.next(), Some(Err(AocError::Parse { line: 2, .. }))));
+  }
+
+  #[test]
+  fn test_entry_iterator()
+  {
+    let entries = EntryIterator::from_string("a b\nc\n\nd\n\n\n\ne\nf\n").collect::<io::Result<Vec<_>>>();
+    assert_eq!(entries.unwrap(), vec!["a b c", "d", "e f"]);
+
+    let entries = EntryIterator::new_with_separator("\na\nb".as_bytes(), ",").collect::<io::Result<Vec<_>>>();
+    assert_eq!(entries.unwrap(), vec!["a,b"]);
+  }
 }
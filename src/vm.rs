@@ -0,0 +1,409 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{self, Display};
+
+pub mod asm;
+pub mod cfg;
+pub mod debugger;
+pub mod ext;
+pub mod trace;
+
+use trace::{Trace, TraceEntry};
+
+/// An instruction of the handheld game console's boot code
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum Instruction
+{
+  /// Add the argument to the accumulator
+  ACC(i32),
+  /// Jump relative to this instruction
+  JMP(i32),
+  /// Do nothing
+  NOP(i32),
+}
+
+impl Instruction
+{
+  /// Swap a `jmp` for a `nop` or the other way around
+  pub fn flipped(self) -> Option<Instruction>
+  {
+    match self {
+      Instruction::JMP(num) => Some(Instruction::NOP(num)),
+      Instruction::NOP(num) => Some(Instruction::JMP(num)),
+      Instruction::ACC(_) => None
+    }
+  }
+}
+
+/// Written like "acc +1"
+impl Display for Instruction
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match self {
+      Instruction::ACC(num) => write!(f, "acc {:+}", num),
+      Instruction::JMP(num) => write!(f, "jmp {:+}", num),
+      Instruction::NOP(num) => write!(f, "nop {:+}", num)
+    }
+  }
+}
+
+impl Execute for Instruction
+{
+  fn execute(&self, state: &mut ExecutionState)
+  {
+    match *self {
+      Instruction::ACC(num) => {
+        state.acc = state.acc.wrapping_add(num);
+        state.pc += 1;
+      }
+      Instruction::JMP(num) => state.pc = state.pc.wrapping_add(num),
+      Instruction::NOP(_) => state.pc += 1
+    }
+  }
+}
+
+/// The name of the accumulator register
+pub const ACC: &str = "acc";
+
+/// The registers of the console, and everything it has output.
+///
+/// Besides the program counter and the accumulator there can be any number of named registers,
+/// which start out as 0. The boot code only uses the accumulator.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct ExecutionState
+{
+  /// The address of the last executed instruction, or -1 if nothing has run yet
+  pub last_pc: i32,
+  pub pc: i32,
+  acc: i32,
+  /// The registers other than the accumulator
+  registers: BTreeMap<String, i32>,
+  pub output: Vec<i32>,
+}
+
+impl ExecutionState
+{
+  pub fn new() -> Self
+  {
+    ExecutionState { last_pc: -1, pc: 0, acc: 0, registers: BTreeMap::new(), output: vec![] }
+  }
+
+  pub fn register(&self, name: &str) -> i32
+  {
+    match name {
+      ACC => self.acc,
+      _ => self.registers.get(name).copied().unwrap_or(0)
+    }
+  }
+
+  pub fn set_register(&mut self, name: &str, value: i32)
+  {
+    match (name, self.registers.get_mut(name)) {
+      (ACC, _) => self.acc = value,
+      (_, Some(register)) => *register = value,
+      (_, None) => { self.registers.insert(name.to_string(), value); }
+    }
+  }
+
+  /// The accumulator, followed by the other registers that have been written to, by name
+  pub fn registers(&self) -> impl Iterator<Item=(&str, i32)>
+  {
+    let others = self.registers.iter().map(|(name, &value)| (name.as_str(), value));
+    std::iter::once((ACC, self.acc)).chain(others)
+  }
+
+  pub fn acc(&self) -> i32
+  {
+    self.acc
+  }
+
+  /// Execute an instruction in place
+  pub fn execute<I>(&mut self, instruction: &I)
+    where I: Execute
+  {
+    self.last_pc = self.pc;
+    instruction.execute(self);
+  }
+
+  /// The state after executing an instruction, leaving this one as it is
+  pub fn execute_instruction<I>(&self, instruction: &I) -> ExecutionState
+    where I: Execute
+  {
+    let mut state = self.clone();
+    state.execute(instruction);
+    state
+  }
+}
+
+/// Something the console can execute
+pub trait Execute: Clone + Display
+{
+  /// Update the registers, and move the program counter to the next instruction to run
+  fn execute(&self, state: &mut ExecutionState);
+}
+
+/// Why a machine stopped running
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Halt
+{
+  /// The program ended by going to the address right after its last instruction
+  Terminated,
+  /// The program went to an address outside of itself
+  OutOfBounds,
+  /// The next instruction has already been executed once
+  Loop,
+  /// The machine has executed as many instructions as it was allowed to
+  StepLimit,
+}
+
+impl Display for Halt
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match self {
+      Halt::Terminated => write!(f, "the program terminated"),
+      Halt::OutOfBounds => write!(f, "jumped outside of the program"),
+      Halt::Loop => write!(f, "about to execute an instruction a second time"),
+      Halt::StepLimit => write!(f, "reached the step limit")
+    }
+  }
+}
+
+/// A reason for a machine to stop before executing its next instruction
+pub trait HaltCondition
+{
+  /// Check the state the machine is in, after `steps` executed instructions
+  fn check(&mut self, state: &ExecutionState, steps: usize) -> Option<Halt>;
+}
+
+/// Halts before any instruction is executed a second time
+#[derive(Debug, Default)]
+pub struct LoopDetection
+{
+  visited: HashSet<i32>,
+}
+
+impl LoopDetection
+{
+  pub fn new() -> Self
+  {
+    LoopDetection { visited: HashSet::new() }
+  }
+}
+
+impl HaltCondition for LoopDetection
+{
+  fn check(&mut self, state: &ExecutionState, _steps: usize) -> Option<Halt>
+  {
+    if self.visited.insert(state.pc) { None } else { Some(Halt::Loop) }
+  }
+}
+
+/// Halts once a given amount of instructions have been executed
+#[derive(Debug, Clone, Copy)]
+pub struct StepLimit(pub usize);
+
+impl HaltCondition for StepLimit
+{
+  fn check(&mut self, _state: &ExecutionState, steps: usize) -> Option<Halt>
+  {
+    if steps >= self.0 { Some(Halt::StepLimit) } else { None }
+  }
+}
+
+/// A console running a program.
+///
+/// The machine always halts when the program counter leaves the program. Anything else that should
+/// stop it, like running into a loop, is added as a `HaltCondition`.
+pub struct Machine<I = Instruction>
+{
+  program: Vec<I>,
+  state: ExecutionState,
+  steps: usize,
+  conditions: Vec<Box<dyn HaltCondition>>,
+  trace: Option<Trace<I>>,
+}
+
+impl<I> Machine<I>
+  where I: Execute
+{
+  pub fn new(program: Vec<I>) -> Self
+  {
+    Machine { program, state: ExecutionState::new(), steps: 0, conditions: vec![], trace: None }
+  }
+
+  /// Also halt whenever the condition says so
+  pub fn halt_on<C>(mut self, condition: C) -> Self
+    where C: HaltCondition + 'static
+  {
+    self.conditions.push(Box::new(condition));
+    self
+  }
+
+  /// Record every executed instruction in a trace
+  pub fn record_trace(mut self) -> Self
+  {
+    self.trace = Some(Trace::new());
+    self
+  }
+
+  pub fn program(&self) -> &[I]
+  {
+    &self.program
+  }
+
+  pub fn state(&self) -> &ExecutionState
+  {
+    &self.state
+  }
+
+  /// The trace of the run so far, if it's being recorded
+  pub fn trace(&self) -> Option<&Trace<I>>
+  {
+    self.trace.as_ref()
+  }
+
+  /// How many instructions have been executed
+  pub fn steps(&self) -> usize
+  {
+    self.steps
+  }
+
+  /// The instruction that will be executed next, if the program counter is inside the program
+  pub fn next_instruction(&self) -> Option<&I>
+  {
+    if self.state.pc < 0 {
+      return None;
+    }
+    self.program.get(self.state.pc as usize)
+  }
+
+  /// Execute the next instruction, unless the machine should halt instead
+  pub fn step(&mut self) -> Result<&ExecutionState, Halt>
+  {
+    self.check_halt().inspect_err(|&halt| {
+      if let Some(trace) = &mut self.trace {
+        trace.halt = Some(halt);
+      }
+    })?;
+
+    let (pc, acc_before) = (self.state.pc, self.state.acc());
+    let instruction = &self.program[pc as usize];
+    self.state.execute(instruction);
+    self.steps += 1;
+    if let Some(trace) = &mut self.trace {
+      trace.entries.push(TraceEntry { pc, instruction: instruction.clone(), acc_before, acc_after: self.state.acc() });
+    }
+    Ok(&self.state)
+  }
+
+  /// Check whether the machine should halt instead of executing the next instruction
+  fn check_halt(&mut self) -> Result<(), Halt>
+  {
+    if self.next_instruction().is_none() {
+      return Err(if self.state.pc == self.program.len() as i32 { Halt::Terminated } else { Halt::OutOfBounds });
+    }
+    let (state, steps) = (&self.state, self.steps);
+    match self.conditions.iter_mut().find_map(|c| c.check(state, steps)) {
+      Some(halt) => Err(halt),
+      None => Ok(())
+    }
+  }
+
+  /// Execute instructions until the machine halts
+  pub fn run(&mut self) -> Halt
+  {
+    loop {
+      if let Err(halt) = self.step() {
+        return halt;
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  /// (last_pc, pc, acc)
+  fn registers(state: &ExecutionState) -> (i32, i32, i32)
+  {
+    (state.last_pc, state.pc, state.acc())
+  }
+
+  #[test]
+  fn test_execute_instruction()
+  {
+    let mut state = ExecutionState::new();
+    let instructions = vec![
+      Instruction::ACC(2),
+      Instruction::JMP(20),
+      Instruction::ACC(-30),
+      Instruction::NOP(0),
+      Instruction::JMP(-4),
+    ];
+    let mut states: Vec<ExecutionState> = vec![];
+
+    for i in instructions {
+      state = state.execute_instruction(&i);
+      states.push(state.clone())
+    }
+
+    assert_eq!(registers(&states[0]), (0, 1, 2));
+    assert_eq!(registers(&states[1]), (1, 21, 2));
+    assert_eq!(registers(&states[2]), (21, 22, -28));
+    assert_eq!(registers(&states[3]), (22, 23, -28));
+    assert_eq!(registers(&states[4]), (23, 19, -28));
+  }
+
+  #[test]
+  fn test_run()
+  {
+    let mut machine = Machine::new(vec![
+      Instruction::ACC(2),
+      Instruction::JMP(2),
+      Instruction::ACC(-30),
+      Instruction::NOP(0),
+    ]);
+    assert_eq!(machine.run(), Halt::Terminated);
+    assert_eq!(registers(machine.state()), (3, 4, 2));
+    assert_eq!(machine.steps(), 3);
+
+    let mut machine = Machine::new(vec![
+      Instruction::ACC(2),
+      Instruction::JMP(30),
+      Instruction::ACC(-30),
+      Instruction::NOP(0),
+    ]);
+    assert_eq!(machine.run(), Halt::OutOfBounds);
+    assert_eq!(registers(machine.state()), (1, 31, 2));
+  }
+
+  #[test]
+  fn test_halt_conditions()
+  {
+    let program = vec![
+      Instruction::NOP(0),
+      Instruction::ACC(1),
+      Instruction::JMP(4),
+      Instruction::ACC(3),
+      Instruction::JMP(-3),
+      Instruction::ACC(-99),
+      Instruction::ACC(1),
+      Instruction::JMP(-4),
+      Instruction::ACC(6)
+    ];
+
+    let mut machine = Machine::new(program.clone()).halt_on(LoopDetection::new());
+    assert_eq!(machine.run(), Halt::Loop);
+    assert_eq!(registers(machine.state()), (4, 1, 5));
+
+    let mut machine = Machine::new(program).halt_on(LoopDetection::new()).halt_on(StepLimit(3));
+    assert_eq!(machine.step().map(registers), Ok((0, 1, 0)));
+    assert_eq!(machine.run(), Halt::StepLimit);
+    assert_eq!(machine.state().pc, 6);
+    assert_eq!(machine.next_instruction(), Some(&Instruction::ACC(1)));
+  }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use crate::error::AocError;
+use super::Instruction;
+
+/// One line of source, split into its parts
+pub(super) struct Line<'a>
+{
+  /// The 1-based line number
+  pub number: usize,
+  pub text: &'a str,
+  pub label: Option<&'a str>,
+  /// (operation, arguments)
+  pub instruction: Option<(&'a str, Vec<&'a str>)>,
+}
+
+impl Line<'_>
+{
+  pub fn error<R: Display>(&self, reason: R) -> AocError
+  {
+    AocError::parse(self.number, self.text, reason)
+  }
+}
+
+pub(super) fn is_label(s: &str) -> bool
+{
+  !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn split_line(number: usize, text: &str) -> Result<Line<'_>, AocError>
+{
+  let code = text.split('#').next().unwrap_or_default();
+  let (label, code) = match code.split_once(':') {
+    Some((label, code)) => (Some(label.trim()), code),
+    None => (None, code)
+  };
+  if let Some(label) = label.filter(|l| !is_label(l)) {
+    return Err(AocError::parse(number, text, format!("Invalid label {:?}", label)));
+  }
+
+  let mut words = code.split_whitespace();
+  let instruction = words.next().map(|operation| (operation, words.collect()));
+  Ok(Line { number, text, label, instruction })
+}
+
+/// Split source into lines, and find the address of the instruction every label points to.
+///
+/// Every line holds at most one instruction, like "jmp +4", optionally preceded by a label like
+/// "loop:". Everything after a '#' is a comment.
+pub(super) fn read_source(source: &str) -> Result<(Vec<Line<'_>>, HashMap<&str, usize>), AocError>
+{
+  let lines = source.lines().enumerate()
+    .map(|(i, text)| split_line(i + 1, text))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  let mut labels = HashMap::new();
+  let mut address = 0;
+  for line in &lines {
+    if let Some(label) = line.label {
+      if labels.insert(label, address).is_some() {
+        return Err(line.error(format!("Duplicate label {:?}", label)));
+      }
+    }
+    if line.instruction.is_some() {
+      address += 1;
+    }
+  }
+  Ok((lines, labels))
+}
+
+/// The offset of a jump from an instruction at an address, written either as a number or as the
+/// label of the instruction to jump to
+pub(super) fn jump_offset(argument: &str, address: usize, labels: &HashMap<&str, usize>) -> Result<i32, String>
+{
+  match argument.parse::<i32>() {
+    Ok(offset) => Ok(offset),
+    Err(_) if is_label(argument) => match labels.get(argument) {
+      Some(&target) => Ok(target as i32 - address as i32),
+      None => Err(format!("Unknown label {:?}", argument))
+    },
+    Err(e) => Err(format!("Invalid argument {:?}: {}", argument, e))
+  }
+}
+
+/// Turn an instruction at an address into machine code, with jump targets looked up in the labels
+fn assemble_instruction(operation: &str, argument: &str, address: usize, labels: &HashMap<&str, usize>) -> Result<Instruction, String>
+{
+  let offset = match operation {
+    "acc" => argument.parse::<i32>().map_err(|e| format!("Invalid argument {:?}: {}", argument, e))?,
+    _ => jump_offset(argument, address, labels)?
+  };
+
+  match operation {
+    "jmp" => Ok(Instruction::JMP(offset)),
+    "acc" => Ok(Instruction::ACC(offset)),
+    "nop" => Ok(Instruction::NOP(offset)),
+    _ => Err(format!("Invalid operation {:?}", operation))
+  }
+}
+
+/// Parse a single instruction with a numeric argument, like "acc +1"
+pub fn parse_instruction(operation: &str, argument: &str) -> Result<Instruction, String>
+{
+  assemble_instruction(operation, argument, 0, &HashMap::new())
+}
+
+/// Assemble boot code into a program.
+///
+/// Every instruction takes a single argument. The argument of a `jmp` or `nop` may be a label
+/// instead of an offset, to jump to that label's instruction.
+pub fn assemble(source: &str) -> Result<Vec<Instruction>, AocError>
+{
+  let (lines, labels) = read_source(source)?;
+
+  let mut program = vec![];
+  for line in &lines {
+    if let Some((operation, arguments)) = &line.instruction {
+      let instruction = match arguments.as_slice() {
+        [argument] => assemble_instruction(operation, argument, program.len(), &labels).map_err(|e| line.error(e))?,
+        _ => return Err(line.error("Expected an operation and an argument"))
+      };
+      program.push(instruction);
+    }
+  }
+  Ok(program)
+}
+
+/// Write a program out in the same format as the puzzle input
+pub fn to_source(program: &[Instruction]) -> String
+{
+  program.iter().map(|i| format!("{}\n", i)).collect()
+}
+
+/// List a program with the address of every instruction, and the absolute address every `jmp` and
+/// `nop` points to.
+///
+/// The addresses are written as labels, so the listing can be assembled back into the same program.
+pub fn disassemble(program: &[Instruction]) -> String
+{
+  let width = program.len().to_string().len();
+  program.iter().enumerate()
+    .map(|(address, instruction)| {
+      let line = format!("{:0width$}: {}", address, instruction, width = width);
+      match instruction {
+        Instruction::JMP(offset) | Instruction::NOP(offset) =>
+          format!("{:<w$} # -> {:0width$}\n", line, address as i32 + offset, w = width + 13, width = width),
+        Instruction::ACC(_) => format!("{}\n", line)
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::inputs::{InputStore, DEFAULT_NAME};
+
+  #[test]
+  fn test_assemble_line()
+  {
+    assert_eq!(assemble("jmp +2").unwrap(), vec![Instruction::JMP(2)]);
+    assert_eq!(assemble("jmp -30").unwrap(), vec![Instruction::JMP(-30)]);
+
+    assert_eq!(assemble("acc +5").unwrap(), vec![Instruction::ACC(5)]);
+    assert_eq!(assemble("acc -3").unwrap(), vec![Instruction::ACC(-3)]);
+
+    assert_eq!(assemble("nop +0").unwrap(), vec![Instruction::NOP(0)]);
+    assert_eq!(assemble("nop -50").unwrap(), vec![Instruction::NOP(-50)]);
+
+    assert!(assemble("xyz +1").is_err());
+    assert!(assemble("jmp").is_err());
+    assert!(assemble("acc +a").is_err());
+  }
+
+  #[test]
+  fn test_assemble()
+  {
+    let source = "
+# Add 1 until the end
+start:  acc 1
+        nop   end   # not taken
+loop:
+  jmp start
+end: acc -1";
+    assert_eq!(assemble(source).unwrap(), vec![
+      Instruction::ACC(1),
+      Instruction::NOP(2),
+      Instruction::JMP(-2),
+      Instruction::ACC(-1),
+    ]);
+
+    assert!(matches!(assemble("acc +1\njmp nowhere"), Err(AocError::Parse { line: 2, .. })));
+    assert!(matches!(assemble("a: acc +1\na: acc +1"), Err(AocError::Parse { line: 2, .. })));
+    assert!(matches!(assemble("a b: acc +1"), Err(AocError::Parse { line: 1, .. })));
+    assert!(matches!(assemble("a: acc a"), Err(AocError::Parse { line: 1, .. })));
+  }
+
+  #[test]
+  fn test_disassemble()
+  {
+    let program = assemble("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\nacc +6").unwrap();
+    assert_eq!(disassemble(&program), "\
+00: nop +0      # -> 00
+01: acc +1
+02: jmp +4      # -> 06
+03: acc +3
+04: jmp -3      # -> 01
+05: acc -99
+06: acc +1
+07: jmp -4      # -> 03
+08: acc +6
+09: acc +6
+");
+    assert_eq!(assemble(&disassemble(&program)).unwrap(), program);
+  }
+
+  #[test]
+  fn test_round_trip()
+  {
+    let source = InputStore::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data")).read(8, DEFAULT_NAME).unwrap();
+    let program = assemble(&source).unwrap();
+    assert_eq!(to_source(&program), source);
+    assert_eq!(assemble(&disassemble(&program)).unwrap(), program);
+  }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use crate::graph::{Direction, Graph};
+use super::{ExecutionState, Instruction};
+
+/// The address an instruction at an address passes control to
+fn successor(address: usize, instruction: Instruction) -> i32
+{
+  let state = ExecutionState { pc: address as i32, ..ExecutionState::new() };
+  state.execute_instruction(&instruction).pc
+}
+
+/// The control flow graph of a program, with an edge from every instruction to the one that runs
+/// after it.
+///
+/// The address right after the last instruction, where the program terminates, is a node too.
+/// Jumps to anywhere else outside the program have no edge.
+pub struct ControlFlow
+{
+  /// Nodes are added in address order, so every node's ID is its address
+  graph: Graph<usize, ()>,
+}
+
+impl ControlFlow
+{
+  pub fn new(program: &[Instruction]) -> Self
+  {
+    let mut graph = Graph::new();
+    for address in 0..=program.len() {
+      graph.add_node(address);
+    }
+    for (address, &instruction) in program.iter().enumerate() {
+      let next = successor(address, instruction);
+      if next >= 0 && next as usize <= program.len() {
+        graph.add_edge(address, next as usize, ());
+      }
+    }
+    ControlFlow { graph }
+  }
+
+  /// The address where the program terminates
+  pub fn end(&self) -> usize
+  {
+    self.graph.len() - 1
+  }
+
+  /// The addresses that are run when starting from the first instruction
+  pub fn reachable_from_start(&self) -> HashSet<usize>
+  {
+    self.graph.bfs(0, Direction::Outgoing).collect()
+  }
+
+  /// The addresses the program terminates from, including the end itself
+  pub fn reaching_end(&self) -> HashSet<usize>
+  {
+    let mut reaching = self.graph.reachable(self.end(), Direction::Incoming);
+    reaching.insert(self.end());
+    reaching
+  }
+}
+
+/// Find the single `jmp` or `nop` that makes a looping program terminate when flipped, as its address
+/// and the flipped instruction.
+///
+/// Only instructions that are run from the start can be the broken one, and flipping one fixes the
+/// program if the flipped instruction leads to an address the program terminates from. The path
+/// from there can't pass the flipped instruction again, since then the unpatched program would
+/// have terminated too.
+pub fn find_patch(program: &[Instruction]) -> Option<(usize, Instruction)>
+{
+  let flow = ControlFlow::new(program);
+  let run = flow.reachable_from_start();
+  let reaching_end = flow.reaching_end();
+
+  (0..program.len())
+    .filter(|address| run.contains(address))
+    .find_map(|address| {
+      let flipped = program[address].flipped()?;
+      let next = successor(address, flipped);
+      if next >= 0 && reaching_end.contains(&(next as usize)) { Some((address, flipped)) } else { None }
+    })
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::vm::asm::assemble;
+
+  #[test]
+  fn test_control_flow()
+  {
+    let program = assemble("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\njmp +10").unwrap();
+    let flow = ControlFlow::new(&program);
+    assert_eq!(flow.end(), 10);
+    assert_eq!(flow.reachable_from_start(), vec![0, 1, 2, 3, 4, 6, 7].into_iter().collect());
+    assert_eq!(flow.reaching_end(), vec![10].into_iter().collect());
+  }
+
+  #[test]
+  fn test_find_patch()
+  {
+    let program = assemble("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6").unwrap();
+    assert_eq!(find_patch(&program), Some((7, Instruction::NOP(-4))));
+
+    let program = assemble("jmp +0\njmp -1").unwrap();
+    assert_eq!(find_patch(&program), None);
+  }
+}
@@ -0,0 +1,315 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+use super::{Execute, ExecutionState, Instruction, Machine};
+use super::asm::is_label;
+
+/// How many executed instructions the debugger remembers
+pub const HISTORY_SIZE: usize = 1000;
+
+/// Where execution should stop when continuing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint
+{
+  /// Before executing the instruction at an address
+  Pc(i32),
+  /// When the accumulator changes to a value
+  Acc(i32),
+}
+
+impl Breakpoint
+{
+  /// Whether the step that left the machine in `state` hits the breakpoint, given the accumulator
+  /// before the step
+  fn hit(&self, previous_acc: i32, state: &ExecutionState) -> bool
+  {
+    match *self {
+      Breakpoint::Pc(pc) => state.pc == pc,
+      Breakpoint::Acc(acc) => previous_acc != acc && state.acc() == acc
+    }
+  }
+}
+
+impl Display for Breakpoint
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match self {
+      Breakpoint::Pc(pc) => write!(f, "pc {}", pc),
+      Breakpoint::Acc(acc) => write!(f, "acc {}", acc)
+    }
+  }
+}
+
+/// An expression on a register, like "acc" or "acc > 100"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Watch
+{
+  register: String,
+  comparison: Option<(String, i32)>,
+}
+
+impl Watch
+{
+  pub fn parse(expression: &str) -> Result<Self, String>
+  {
+    let words = expression.split_whitespace().collect::<Vec<_>>();
+    match words.as_slice() {
+      [register] if is_label(register) => Ok(Watch { register: register.to_string(), comparison: None }),
+      [register, op @ ("==" | "!=" | "<" | "<=" | ">" | ">="), value] if is_label(register) => {
+        let value = value.parse().map_err(|e| format!("Invalid value {:?}: {}", value, e))?;
+        Ok(Watch { register: register.to_string(), comparison: Some((op.to_string(), value)) })
+      }
+      _ => Err(format!("Invalid watch expression {:?}, expected \"<register>\" or \"<register> <op> <value>\"", expression))
+    }
+  }
+
+  pub fn evaluate(&self, state: &ExecutionState) -> String
+  {
+    let register = state.register(&self.register);
+    match &self.comparison {
+      None => register.to_string(),
+      Some((op, value)) => match op.as_str() {
+        "==" => register == *value,
+        "!=" => register != *value,
+        "<" => register < *value,
+        "<=" => register <= *value,
+        ">" => register > *value,
+        _ => register >= *value
+      }.to_string()
+    }
+  }
+}
+
+impl Display for Watch
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match &self.comparison {
+      None => write!(f, "{}", self.register),
+      Some((op, value)) => write!(f, "{} {} {}", self.register, op, value)
+    }
+  }
+}
+
+const HELP: &str = "Commands:
+  step [N], s      Execute the next N instructions (default: 1)
+  continue, c      Execute until a breakpoint, a watch changing, or the machine halting
+  break pc|acc N   Stop when the program counter reaches N, or the accumulator changes to N
+  watch EXPR       Stop when EXPR changes, where EXPR is a register like \"acc\", or \"<register> <op> N\"
+  delete N         Remove breakpoint or watch N
+  list             List the breakpoints and watches
+  history [N]      Show the last N executed addresses (default: 10)
+  print, p         Show the registers and the next instruction";
+
+/// A debugger controlling a machine through text commands
+pub struct Debugger<I = Instruction>
+{
+  machine: Machine<I>,
+  breakpoints: Vec<Breakpoint>,
+  watches: Vec<Watch>,
+  /// The addresses of the last executed instructions, oldest first
+  history: VecDeque<i32>,
+}
+
+impl<I> Debugger<I>
+  where I: Execute
+{
+  pub fn new(machine: Machine<I>) -> Self
+  {
+    Debugger { machine, breakpoints: vec![], watches: vec![], history: VecDeque::new() }
+  }
+
+  pub fn machine(&self) -> &Machine<I>
+  {
+    &self.machine
+  }
+
+  pub fn add_breakpoint(&mut self, breakpoint: Breakpoint)
+  {
+    self.breakpoints.push(breakpoint);
+  }
+
+  pub fn add_watch(&mut self, watch: Watch)
+  {
+    self.watches.push(watch);
+  }
+
+  /// Run a command, and return what it has to say
+  pub fn execute(&mut self, command: &str) -> Result<String, String>
+  {
+    let words = command.split_whitespace().collect::<Vec<_>>();
+    let number = |word: Option<&&str>| -> Result<Option<i32>, String> {
+      word.map(|w| w.parse().map_err(|e| format!("Invalid number {:?}: {}", w, e))).transpose()
+    };
+
+    match words.as_slice() {
+      [] => Ok(String::new()),
+      ["step" | "s", rest @ ..] if rest.len() <= 1 => {
+        let steps = number(rest.first())?.unwrap_or(1);
+        Ok(self.run(Some(steps.max(0) as usize)))
+      }
+      ["continue" | "c"] => Ok(self.run(None)),
+      ["break", kind, value] => {
+        let value = number(Some(value))?.unwrap_or_default();
+        let breakpoint = match *kind {
+          "pc" => Breakpoint::Pc(value),
+          "acc" => Breakpoint::Acc(value),
+          _ => return Err(format!("Can only break on pc or acc, not {:?}", kind))
+        };
+        self.add_breakpoint(breakpoint);
+        Ok(format!("Breakpoint {}: {}", self.breakpoints.len() - 1, breakpoint))
+      }
+      ["watch", ..] => {
+        let watch = Watch::parse(command.trim_start()["watch".len()..].trim())?;
+        let value = watch.evaluate(self.machine.state());
+        let output = format!("Watch {}: {} = {}", self.breakpoints.len() + self.watches.len(), watch, value);
+        self.add_watch(watch);
+        Ok(output)
+      }
+      ["delete", n] => {
+        let n = number(Some(n))?.unwrap_or_default() as usize;
+        if n < self.breakpoints.len() {
+          self.breakpoints.remove(n);
+        } else if n - self.breakpoints.len() < self.watches.len() {
+          self.watches.remove(n - self.breakpoints.len());
+        } else {
+          return Err(format!("No breakpoint or watch {}", n));
+        }
+        Ok(format!("Deleted {}", n))
+      }
+      ["list"] => Ok(self.list()),
+      ["history", rest @ ..] if rest.len() <= 1 => {
+        let n = number(rest.first())?.unwrap_or(10).max(0) as usize;
+        let skip = self.history.len().saturating_sub(n);
+        Ok(self.history.iter().skip(skip).map(|pc| pc.to_string()).collect::<Vec<_>>().join("\n"))
+      }
+      ["print" | "p"] => Ok(self.describe_state()),
+      ["help" | "h"] => Ok(HELP.to_string()),
+      _ => Err(format!("Unknown command {:?}, try \"help\"", command.trim()))
+    }
+  }
+
+  /// Execute instructions until something stops the machine, or until it has taken the given amount
+  /// of steps
+  fn run(&mut self, max_steps: Option<usize>) -> String
+  {
+    let mut output = vec![];
+    let mut values = self.watch_values();
+    let mut steps = 0;
+
+    while max_steps != Some(steps) {
+      let pc = self.machine.state().pc;
+      let acc = self.machine.state().acc();
+      if let Err(halt) = self.machine.step() {
+        output.push(format!("Halted: {}", halt));
+        break;
+      }
+      self.history.push_back(pc);
+      if self.history.len() > HISTORY_SIZE {
+        self.history.pop_front();
+      }
+      steps += 1;
+
+      let new_values = self.watch_values();
+      let changed = values.iter().zip(&new_values).enumerate().filter(|(_, (old, new))| old != new).collect::<Vec<_>>();
+      for (i, (old, new)) in &changed {
+        output.push(format!("Watch {}: {}: {} -> {}", self.breakpoints.len() + i, self.watches[*i], old, new));
+      }
+      let state = self.machine.state();
+      let hit = self.breakpoints.iter().enumerate().filter(|(_, b)| b.hit(acc, state)).collect::<Vec<_>>();
+      for (i, breakpoint) in &hit {
+        output.push(format!("Breakpoint {}: {}", i, breakpoint));
+      }
+      if !changed.is_empty() || !hit.is_empty() {
+        break;
+      }
+      values = new_values;
+    }
+
+    output.push(self.describe_state());
+    output.join("\n")
+  }
+
+  fn watch_values(&self) -> Vec<String>
+  {
+    self.watches.iter().map(|w| w.evaluate(self.machine.state())).collect()
+  }
+
+  fn list(&self) -> String
+  {
+    let breakpoints = self.breakpoints.iter().map(|b| b.to_string());
+    let watches = self.watches.iter().map(|w| format!("watch {}", w));
+    breakpoints.chain(watches).enumerate().map(|(i, s)| format!("{}: {}", i, s)).collect::<Vec<_>>().join("\n")
+  }
+
+  fn describe_state(&self) -> String
+  {
+    let state = self.machine.state();
+    let next = match self.machine.next_instruction() {
+      Some(instruction) => format!("next: {}", instruction),
+      None => "outside the program".to_string()
+    };
+    format!("pc {}, acc {}, {} steps, {}", state.pc, state.acc(), self.machine.steps(), next)
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::vm::asm::assemble;
+  use crate::vm::LoopDetection;
+
+  fn debugger() -> Debugger
+  {
+    let program = assemble("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6").unwrap();
+    Debugger::new(Machine::new(program).halt_on(LoopDetection::new()))
+  }
+
+  #[test]
+  fn test_step_and_history()
+  {
+    let mut debugger = debugger();
+    assert_eq!(debugger.execute("step").unwrap(), "pc 1, acc 0, 1 steps, next: acc +1");
+    assert_eq!(debugger.execute("s 3").unwrap(), "pc 7, acc 2, 4 steps, next: jmp -4");
+    assert_eq!(debugger.execute("history 2").unwrap(), "2\n6");
+    assert_eq!(debugger.execute("c").unwrap(), "Halted: about to execute an instruction a second time\npc 1, acc 5, 7 steps, next: acc +1");
+    assert!(debugger.execute("jump 5").is_err());
+  }
+
+  #[test]
+  fn test_breakpoints()
+  {
+    let mut debugger = debugger();
+    assert_eq!(debugger.execute("break pc 3").unwrap(), "Breakpoint 0: pc 3");
+    assert_eq!(debugger.execute("break acc 5").unwrap(), "Breakpoint 1: acc 5");
+    assert_eq!(debugger.execute("continue").unwrap(), "Breakpoint 0: pc 3\npc 3, acc 2, 5 steps, next: acc +3");
+    assert_eq!(debugger.execute("continue").unwrap(), "Breakpoint 1: acc 5\npc 4, acc 5, 6 steps, next: jmp -3");
+    assert_eq!(debugger.execute("continue").unwrap(), "Halted: about to execute an instruction a second time\npc 1, acc 5, 7 steps, next: acc +1");
+    assert_eq!(debugger.execute("delete 0").unwrap(), "Deleted 0");
+    assert_eq!(debugger.execute("list").unwrap(), "0: acc 5");
+    assert!(debugger.execute("break sp 1").is_err());
+  }
+
+  #[test]
+  fn test_watches()
+  {
+    let mut debugger = debugger();
+    assert_eq!(debugger.execute("watch acc > 1").unwrap(), "Watch 0: acc > 1 = false");
+    assert_eq!(debugger.execute("watch acc").unwrap(), "Watch 1: acc = 0");
+    assert_eq!(debugger.execute("c").unwrap(), "Watch 1: acc: 0 -> 1\npc 2, acc 1, 2 steps, next: jmp +4");
+    assert_eq!(debugger.execute("delete 1").unwrap(), "Deleted 1");
+    assert_eq!(debugger.execute("c").unwrap(), "Watch 0: acc > 1: false -> true\npc 7, acc 2, 4 steps, next: jmp -4");
+    assert!(Watch::parse("acc ~ 1").is_err());
+    assert!(Watch::parse("a-b").is_err());
+  }
+
+  #[test]
+  fn test_watch_registers()
+  {
+    let program = crate::vm::ext::InstructionSet::extended().assemble("set n 3\nloop: add n -1\njnz n loop").unwrap();
+    let mut debugger = Debugger::new(Machine::new(program));
+    assert_eq!(debugger.execute("watch n == 1").unwrap(), "Watch 0: n == 1 = false");
+    assert_eq!(debugger.execute("c").unwrap(), "Watch 0: n == 1: false -> true\npc 2, acc 0, 4 steps, next: jnz n -1");
+  }
+}
@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+use std::rc::Rc;
+use crate::error::AocError;
+use super::asm::{is_label, jump_offset, read_source};
+use super::{Execute, ExecutionState, Instruction};
+
+/// An argument of an instruction, either a register or a number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand
+{
+  Register(String),
+  Value(i32),
+}
+
+impl Operand
+{
+  pub fn parse(s: &str) -> Result<Self, String>
+  {
+    match s.parse() {
+      Ok(value) => Ok(Operand::Value(value)),
+      Err(_) if is_label(s) => Ok(Operand::Register(s.to_string())),
+      Err(e) => Err(format!("Invalid argument {:?}: {}", s, e))
+    }
+  }
+
+  /// The value of a number, or what's in a register
+  pub fn get(&self, state: &ExecutionState) -> i32
+  {
+    match self {
+      Operand::Register(name) => state.register(name),
+      Operand::Value(value) => *value
+    }
+  }
+}
+
+impl Display for Operand
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match self {
+      Operand::Register(name) => write!(f, "{}", name),
+      Operand::Value(value) => write!(f, "{:+}", value)
+    }
+  }
+}
+
+/// An opcode, defining what instructions using it do
+pub trait Operation
+{
+  /// The name the opcode is written with, like "jmp"
+  fn name(&self) -> &'static str;
+
+  /// How many arguments instructions take
+  fn arity(&self) -> usize;
+
+  /// The argument that's a jump offset, if any, which may be written as a label instead
+  fn jump_argument(&self) -> Option<usize>
+  {
+    None
+  }
+
+  /// Check that the arguments make sense, like a register being given where one is written to
+  fn check(&self, _args: &[Operand]) -> Result<(), String>
+  {
+    Ok(())
+  }
+
+  /// Update the registers, and move the program counter to the next instruction to run
+  fn execute(&self, args: &[Operand], state: &mut ExecutionState);
+}
+
+/// An instruction from an `InstructionSet`
+#[derive(Clone)]
+pub struct Op
+{
+  operation: Rc<dyn Operation>,
+  args: Vec<Operand>,
+}
+
+impl Op
+{
+  pub fn name(&self) -> &'static str
+  {
+    self.operation.name()
+  }
+
+  pub fn args(&self) -> &[Operand]
+  {
+    &self.args
+  }
+}
+
+impl Execute for Op
+{
+  fn execute(&self, state: &mut ExecutionState)
+  {
+    self.operation.execute(&self.args, state)
+  }
+}
+
+/// Written like "mul b +3"
+impl Display for Op
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    write!(f, "{}", self.name())?;
+    for arg in &self.args {
+      write!(f, " {}", arg)?;
+    }
+    Ok(())
+  }
+}
+
+impl Debug for Op
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    write!(f, "Op({})", self)
+  }
+}
+
+impl PartialEq for Op
+{
+  fn eq(&self, other: &Self) -> bool
+  {
+    self.name() == other.name() && self.args == other.args
+  }
+}
+
+fn register(args: &[Operand], i: usize) -> Result<(), String>
+{
+  match args.get(i) {
+    Some(Operand::Register(_)) => Ok(()),
+    _ => Err(format!("Argument {} must be a register", i + 1))
+  }
+}
+
+/// `acc X`: add X to the accumulator, like `Instruction::ACC`
+pub struct Acc;
+
+impl Operation for Acc
+{
+  fn name(&self) -> &'static str { "acc" }
+  fn arity(&self) -> usize { 1 }
+
+  fn execute(&self, args: &[Operand], state: &mut ExecutionState)
+  {
+    Instruction::ACC(args[0].get(state)).execute(state);
+  }
+}
+
+/// `jmp X`: jump X instructions away, like `Instruction::JMP`
+pub struct Jmp;
+
+impl Operation for Jmp
+{
+  fn name(&self) -> &'static str { "jmp" }
+  fn arity(&self) -> usize { 1 }
+  fn jump_argument(&self) -> Option<usize> { Some(0) }
+
+  fn execute(&self, args: &[Operand], state: &mut ExecutionState)
+  {
+    Instruction::JMP(args[0].get(state)).execute(state);
+  }
+}
+
+/// `nop X`: do nothing, like `Instruction::NOP`
+pub struct Nop;
+
+impl Operation for Nop
+{
+  fn name(&self) -> &'static str { "nop" }
+  fn arity(&self) -> usize { 1 }
+  fn jump_argument(&self) -> Option<usize> { Some(0) }
+
+  fn execute(&self, args: &[Operand], state: &mut ExecutionState)
+  {
+    Instruction::NOP(args[0].get(state)).execute(state);
+  }
+}
+
+/// `<name> R X`: store the result of combining register R with X in R, like `mul R X`. `f` should
+/// wrap around on overflow rather than panic.
+pub struct Arithmetic
+{
+  pub name: &'static str,
+  pub f: fn(i32, i32) -> i32,
+}
+
+impl Operation for Arithmetic
+{
+  fn name(&self) -> &'static str { self.name }
+  fn arity(&self) -> usize { 2 }
+  fn check(&self, args: &[Operand]) -> Result<(), String> { register(args, 0) }
+
+  fn execute(&self, args: &[Operand], state: &mut ExecutionState)
+  {
+    if let Operand::Register(name) = &args[0] {
+      let value = (self.f)(state.register(name), args[1].get(state));
+      state.set_register(name, value);
+    }
+    state.pc += 1;
+  }
+}
+
+/// `<name> X Y`: jump Y instructions away if X meets the condition, like `jnz X Y`
+pub struct ConditionalJump
+{
+  pub name: &'static str,
+  pub condition: fn(i32) -> bool,
+}
+
+impl Operation for ConditionalJump
+{
+  fn name(&self) -> &'static str { self.name }
+  fn arity(&self) -> usize { 2 }
+  fn jump_argument(&self) -> Option<usize> { Some(1) }
+
+  fn execute(&self, args: &[Operand], state: &mut ExecutionState)
+  {
+    let offset = if (self.condition)(args[0].get(state)) { args[1].get(state) } else { 1 };
+    state.pc = state.pc.wrapping_add(offset);
+  }
+}
+
+/// `out X`: output X
+pub struct Out;
+
+impl Operation for Out
+{
+  fn name(&self) -> &'static str { "out" }
+  fn arity(&self) -> usize { 1 }
+
+  fn execute(&self, args: &[Operand], state: &mut ExecutionState)
+  {
+    let value = args[0].get(state);
+    state.output.push(value);
+    state.pc += 1;
+  }
+}
+
+/// The opcodes a program may use, by name
+#[derive(Clone, Default)]
+pub struct InstructionSet
+{
+  operations: HashMap<&'static str, Rc<dyn Operation>>,
+}
+
+impl InstructionSet
+{
+  /// An instruction set without any opcodes
+  pub fn new() -> Self
+  {
+    InstructionSet { operations: HashMap::new() }
+  }
+
+  /// The boot code's `acc`, `jmp` and `nop`
+  pub fn boot_code() -> Self
+  {
+    let mut set = Self::new();
+    set.register(Acc);
+    set.register(Jmp);
+    set.register(Nop);
+    set
+  }
+
+  /// The boot code's opcodes, plus `set`, `add`, `mul`, `jnz`, `jgz` and `out`
+  pub fn extended() -> Self
+  {
+    let mut set = Self::boot_code();
+    set.register(Arithmetic { name: "set", f: |_, x| x });
+    set.register(Arithmetic { name: "add", f: i32::wrapping_add });
+    set.register(Arithmetic { name: "mul", f: i32::wrapping_mul });
+    set.register(ConditionalJump { name: "jnz", condition: |x| x != 0 });
+    set.register(ConditionalJump { name: "jgz", condition: |x| x > 0 });
+    set.register(Out);
+    set
+  }
+
+  /// Add an opcode, replacing any opcode with the same name
+  pub fn register<O>(&mut self, operation: O)
+    where O: Operation + 'static
+  {
+    self.operations.insert(operation.name(), Rc::new(operation));
+  }
+
+  /// Create an instruction, checking that it has the right arguments
+  pub fn instruction(&self, name: &str, args: Vec<Operand>) -> Result<Op, String>
+  {
+    let operation = self.operations.get(name).ok_or_else(|| format!("Invalid operation {:?}", name))?;
+    if args.len() != operation.arity() {
+      return Err(format!("{} takes {} arguments, not {}", name, operation.arity(), args.len()));
+    }
+    operation.check(&args)?;
+    Ok(Op { operation: operation.clone(), args })
+  }
+
+  /// Assemble a program, in the same format as `asm::assemble` except that instructions can have any
+  /// number of arguments. Jump offsets are numbers or labels, and other arguments are numbers or
+  /// registers.
+  pub fn assemble(&self, source: &str) -> Result<Vec<Op>, AocError>
+  {
+    let (lines, labels) = read_source(source)?;
+
+    let mut program = vec![];
+    for line in &lines {
+      if let Some((name, arguments)) = &line.instruction {
+        let jump = self.operations.get(name).and_then(|o| o.jump_argument());
+        let args = arguments.iter().enumerate()
+          .map(|(i, arg)| match jump == Some(i) {
+            true => jump_offset(arg, program.len(), &labels).map(Operand::Value),
+            false => Operand::parse(arg)
+          })
+          .collect::<Result<Vec<_>, _>>()
+          .map_err(|e| line.error(e))?;
+        program.push(self.instruction(name, args).map_err(|e| line.error(e))?);
+      }
+    }
+    Ok(program)
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::vm::{Halt, LoopDetection, Machine};
+
+  #[test]
+  fn test_boot_code()
+  {
+    let program = InstructionSet::boot_code().assemble("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6").unwrap();
+    let mut machine = Machine::new(program).halt_on(LoopDetection::new());
+    assert_eq!(machine.run(), Halt::Loop);
+    assert_eq!(machine.state().acc(), 5);
+    assert!(InstructionSet::boot_code().assemble("mul a 2").is_err());
+  }
+
+  #[test]
+  fn test_extended()
+  {
+    let source = "
+  set n 5
+  set f 1
+loop:
+  mul f n
+  add n -1
+  out f
+  jgz n loop
+  acc f";
+    let program = InstructionSet::extended().assemble(source).unwrap();
+    assert_eq!(program[5].to_string(), "jgz n -3");
+
+    let mut machine = Machine::new(program);
+    assert_eq!(machine.run(), Halt::Terminated);
+    assert_eq!(machine.state().output, vec![5, 20, 60, 120, 120]);
+    assert_eq!(machine.state().registers().collect::<Vec<_>>(), vec![("acc", 120), ("f", 120), ("n", 0)]);
+
+    let set = InstructionSet::extended();
+    assert!(matches!(set.assemble("set 1 2"), Err(AocError::Parse { line: 1, .. })));
+    assert!(matches!(set.assemble("out\nout 1"), Err(AocError::Parse { line: 1, .. })));
+    assert!(matches!(set.assemble("set n 1\njnz n nowhere"), Err(AocError::Parse { line: 2, .. })));
+  }
+
+  #[test]
+  fn test_overflow()
+  {
+    let mut machine = Machine::new(InstructionSet::extended().assemble("set a 2147483647\nmul a 2\nadd a 3\nacc 2147483647\nacc 1").unwrap());
+    assert_eq!(machine.run(), Halt::Terminated);
+    assert_eq!(machine.state().register("a"), 1);
+    assert_eq!(machine.state().acc(), i32::MIN);
+
+    let mut machine = Machine::new(InstructionSet::extended().assemble("nop 0\njgz 1 2147483647").unwrap());
+    assert_eq!(machine.run(), Halt::OutOfBounds);
+  }
+
+  #[test]
+  fn test_register()
+  {
+    /// `dbl R`: double register R
+    struct Double;
+
+    impl Operation for Double
+    {
+      fn name(&self) -> &'static str { "dbl" }
+      fn arity(&self) -> usize { 1 }
+      fn check(&self, args: &[Operand]) -> Result<(), String> { register(args, 0) }
+
+      fn execute(&self, args: &[Operand], state: &mut ExecutionState)
+      {
+        if let Operand::Register(name) = &args[0] {
+          state.set_register(name, state.register(name).wrapping_mul(2));
+        }
+        state.pc += 1;
+      }
+    }
+
+    let mut set = InstructionSet::boot_code();
+    set.register(Double);
+    let mut machine = Machine::new(set.assemble("acc 21\ndbl acc").unwrap());
+    machine.run();
+    assert_eq!(machine.state().acc(), 42);
+    assert!(set.assemble("dbl 2").is_err());
+  }
+}
@@ -0,0 +1,217 @@
+use std::fmt::{self, Display};
+use crate::error::AocError;
+use super::{Execute, ExecutionState, Halt, Instruction};
+use super::asm::parse_instruction;
+
+/// One executed instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry<I = Instruction>
+{
+  pub pc: i32,
+  pub instruction: I,
+  pub acc_before: i32,
+  pub acc_after: i32,
+}
+
+impl<I: Display> Display for TraceEntry<I>
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    write!(f, "{} {} {} {}", self.pc, self.instruction, self.acc_before, self.acc_after)
+  }
+}
+
+/// Every instruction a machine executed, and why it stopped if it did.
+///
+/// Written as one "<pc> <instruction> <acc before> <acc after>" line per instruction, like
+/// "7 acc +3 2 5", followed by a "halt <reason>" line.
+///
+/// Only the accumulator is recorded. For programs using other registers, like those assembled with
+/// an `ext::InstructionSet`, replaying only checks the accumulator, and two runs only show up as
+/// different once they jump somewhere else or change the accumulator differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<I = Instruction>
+{
+  pub entries: Vec<TraceEntry<I>>,
+  pub halt: Option<Halt>,
+}
+
+const HALT_NAMES: [(Halt, &str); 4] = [
+  (Halt::Terminated, "terminated"),
+  (Halt::OutOfBounds, "out-of-bounds"),
+  (Halt::Loop, "loop"),
+  (Halt::StepLimit, "step-limit"),
+];
+
+fn parse_entry(line: &str) -> Result<TraceEntry, String>
+{
+  let words = line.split_whitespace().collect::<Vec<_>>();
+  let number = |word: &str| word.parse::<i32>().map_err(|e| format!("Invalid number {:?}: {}", word, e));
+  match words.as_slice() {
+    [pc, operation, argument, acc_before, acc_after] => Ok(TraceEntry {
+      pc: number(pc)?,
+      instruction: parse_instruction(operation, argument)?,
+      acc_before: number(acc_before)?,
+      acc_after: number(acc_after)?,
+    }),
+    _ => Err("Expected \"<pc> <instruction> <acc before> <acc after>\"".to_string())
+  }
+}
+
+impl<I> Trace<I>
+{
+  pub fn new() -> Self
+  {
+    Trace { entries: vec![], halt: None }
+  }
+
+  /// Execute the traced instructions again from the start, checking that every step has the
+  /// recorded effect, and return the state the machine ends up in
+  pub fn replay(&self) -> Result<ExecutionState, String>
+    where I: Execute
+  {
+    let mut state = ExecutionState::new();
+    for (step, entry) in self.entries.iter().enumerate() {
+      if (entry.pc, entry.acc_before) != (state.pc, state.acc()) {
+        return Err(format!("Step {} starts at pc {} with acc {}, but the previous step left pc {} and acc {}",
+                           step, entry.pc, entry.acc_before, state.pc, state.acc()));
+      }
+      state.execute(&entry.instruction);
+      if state.acc() != entry.acc_after {
+        return Err(format!("Step {} ({}) should leave acc {}, but it was recorded as {}", step, entry.instruction, state.acc(), entry.acc_after));
+      }
+    }
+    Ok(state)
+  }
+}
+
+impl<I> Default for Trace<I>
+{
+  fn default() -> Self
+  {
+    Self::new()
+  }
+}
+
+impl Trace
+{
+  pub fn parse(text: &str) -> Result<Self, AocError>
+  {
+    let mut trace = Trace::new();
+    for (i, line) in text.lines().enumerate() {
+      if trace.halt.is_some() {
+        return Err(AocError::parse(i + 1, line, "Trace continues after halting"));
+      }
+      match line.strip_prefix("halt ") {
+        Some(name) => {
+          let halt = HALT_NAMES.iter().find(|(_, n)| *n == name.trim()).map(|(halt, _)| *halt);
+          trace.halt = Some(halt.ok_or_else(|| AocError::parse(i + 1, line, format!("Unknown halt reason {:?}", name)))?);
+        }
+        None => trace.entries.push(parse_entry(line).map_err(|e| AocError::parse(i + 1, line, e))?)
+      }
+    }
+    Ok(trace)
+  }
+}
+
+impl<I: Display> Display for Trace<I>
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    for entry in &self.entries {
+      writeln!(f, "{}", entry)?;
+    }
+    if let Some(halt) = self.halt {
+      let name = HALT_NAMES.iter().find(|(h, _)| *h == halt).map_or("", |(_, name)| name);
+      writeln!(f, "halt {}", name)?;
+    }
+    Ok(())
+  }
+}
+
+/// The first step where two traces differ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence<I = Instruction>
+{
+  pub step: usize,
+  /// What each trace did at that step, or None if it had already stopped
+  pub left: Option<TraceEntry<I>>,
+  pub right: Option<TraceEntry<I>>,
+}
+
+impl<I: Display> Display for Divergence<I>
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    let describe = |entry: &Option<TraceEntry<I>>| match entry {
+      Some(e) => format!("pc {}: {} (acc {} -> {})", e.pc, e.instruction, e.acc_before, e.acc_after),
+      None => "halted".to_string()
+    };
+    write!(f, "The runs diverge at step {}:\n< {}\n> {}", self.step, describe(&self.left), describe(&self.right))
+  }
+}
+
+/// Find the first step where two runs did something different, if any
+pub fn diff<I>(left: &Trace<I>, right: &Trace<I>) -> Option<Divergence<I>>
+  where I: Clone + PartialEq
+{
+  let steps = left.entries.len().max(right.entries.len());
+  (0..steps)
+    .map(|step| Divergence { step, left: left.entries.get(step).cloned(), right: right.entries.get(step).cloned() })
+    .find(|d| d.left != d.right)
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::vm::asm::assemble;
+  use crate::vm::{LoopDetection, Machine};
+
+  const PROGRAM: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+
+  fn record(source: &str) -> Trace
+  {
+    let mut machine = Machine::new(assemble(source).unwrap()).halt_on(LoopDetection::new()).record_trace();
+    machine.run();
+    machine.trace().unwrap().clone()
+  }
+
+  #[test]
+  fn test_record()
+  {
+    let trace = record(PROGRAM);
+    assert_eq!(trace.entries.len(), 7);
+    assert_eq!(trace.entries[3], TraceEntry { pc: 6, instruction: Instruction::ACC(1), acc_before: 1, acc_after: 2 });
+    assert_eq!(trace.halt, Some(Halt::Loop));
+    assert_eq!(trace.replay().map(|s| (s.last_pc, s.pc, s.acc())), Ok((4, 1, 5)));
+  }
+
+  #[test]
+  fn test_parse()
+  {
+    let trace = record(PROGRAM);
+    let text = trace.to_string();
+    assert!(text.starts_with("0 nop +0 0 0\n1 acc +1 0 1\n"));
+    assert!(text.ends_with("4 jmp -3 5 5\nhalt loop\n"));
+    assert_eq!(Trace::parse(&text).unwrap(), trace);
+
+    assert!(matches!(Trace::parse("0 nop +0 0"), Err(AocError::Parse { line: 1, .. })));
+    assert!(matches!(Trace::parse("halt loop\n0 nop +0 0 0"), Err(AocError::Parse { line: 2, .. })));
+    assert!(Trace::parse("0 acc +1 0 1\n1 acc +1 0 1").unwrap().replay().is_err());
+  }
+
+  #[test]
+  fn test_diff()
+  {
+    let original = record(PROGRAM);
+    let patched = record(&PROGRAM.replace("jmp -4", "nop -4"));
+    assert_eq!(diff(&original, &original), None);
+
+    let divergence = diff(&original, &patched).unwrap();
+    assert_eq!(divergence.step, 4);
+    assert_eq!(divergence.left.as_ref().map(|e| e.instruction), Some(Instruction::JMP(-4)));
+    assert_eq!(divergence.right.as_ref().map(|e| e.instruction), Some(Instruction::NOP(-4)));
+    assert_eq!(patched.halt, Some(Halt::Terminated));
+  }
+}